@@ -1,12 +1,19 @@
 use std::{
+    error::Error,
     fmt::{self, Display},
-    fs::File,
-    io::Write,
-    ops::{Add, Mul, Sub},
+    fs::{self, File},
+    io::{self, Write},
+    ops::{Add, AddAssign, Mul, Sub, SubAssign},
+    path::Path,
 };
 
+use crate::math::Float;
+
 const RGB: f32 = 255.;
+const BLOOM_RADIUS: f32 = 2.;
+const ASCII_RAMP: &[u8] = b" .:-=+*#%@";
 
+#[allow(dead_code)]
 #[derive(Clone, Copy)]
 enum Primary {
     Red,
@@ -15,24 +22,97 @@ enum Primary {
 }
 
 enum ColorFormat {
+    #[allow(dead_code)]
     Raw(f32, f32, f32),
-    RGB(u8, u8, u8),
+    Rgb(u8, u8, u8),
 }
 
 #[derive(Clone)]
-pub struct Color(f32, f32, f32);
+pub struct Color<T = f32>(T, T, T);
+
+impl<T: Float> Color<T> {
+    pub fn from_channels(red: T, green: T, blue: T) -> Self {
+        Self(red, green, blue)
+    }
+}
 
 impl Color {
     pub fn new(red: f32, green: f32, blue: f32) -> Self {
         Self(red, green, blue)
     }
 
+    // Tanner Helland's fit of the blackbody spectrum, valid from 1000K to 40000K
+    pub fn from_kelvin(temp: f32) -> Self {
+        let t = temp.clamp(1000., 40000.) / 100.;
+
+        let red = if t <= 66. {
+            255.
+        } else {
+            329.698_73 * (t - 60.).powf(-0.133_204_76)
+        };
+        let green = if t <= 66. {
+            99.470_8 * t.ln() - 161.119_57
+        } else {
+            288.122_17 * (t - 60.).powf(-0.075_514_85)
+        };
+        let blue = if t >= 66. {
+            255.
+        } else if t <= 19. {
+            0.
+        } else {
+            138.517_73 * (t - 10.).ln() - 305.044_8
+        };
+
+        Self(
+            red.clamp(0., RGB) / RGB,
+            green.clamp(0., RGB) / RGB,
+            blue.clamp(0., RGB) / RGB,
+        )
+    }
+
+    pub fn srgb_to_linear(&self) -> Self {
+        let decode = |c: f32| {
+            if c <= 0.04045 {
+                c / 12.92
+            } else {
+                ((c + 0.055) / 1.055).powf(2.4)
+            }
+        };
+
+        Self(decode(self.0), decode(self.1), decode(self.2))
+    }
+
+    pub fn linear_to_srgb(&self) -> Self {
+        let encode = |c: f32| {
+            if c <= 0.0031308 {
+                c * 12.92
+            } else {
+                1.055 * c.powf(1. / 2.4) - 0.055
+            }
+        };
+
+        Self(encode(self.0), encode(self.1), encode(self.2))
+    }
+
+    pub fn clamped(&self) -> Self {
+        Self(
+            self.0.clamp(0., 1.),
+            self.1.clamp(0., 1.),
+            self.2.clamp(0., 1.),
+        )
+    }
+
+    pub fn luminance(&self) -> f32 {
+        0.2126 * self.0 + 0.7152 * self.1 + 0.0722 * self.2
+    }
+
+    #[allow(dead_code)]
     fn get_raw(&self) -> ColorFormat {
         ColorFormat::Raw(self.0, self.1, self.2)
     }
 
     fn get_rgb(&self) -> ColorFormat {
-        ColorFormat::RGB(
+        ColorFormat::Rgb(
             (self.0 * RGB) as u8,
             (self.1 * RGB) as u8,
             (self.2 * RGB) as u8,
@@ -40,111 +120,164 @@ impl Color {
     }
 }
 
-impl Add for &Color {
-    type Output = Color;
+impl<T: Float> PartialEq for Color<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1 && self.2 == other.2
+    }
+}
+
+impl<T: Float> Add for &Color<T> {
+    type Output = Color<T>;
 
     fn add(self, rhs: Self) -> Self::Output {
         let red = self.0 + rhs.0;
         let green = self.1 + rhs.1;
         let blue = self.2 + rhs.2;
 
-        Color::new(red, green, blue)
+        Color(red, green, blue)
+    }
+}
+
+impl<T: Float> AddAssign<&Color<T>> for Color<T> {
+    fn add_assign(&mut self, rhs: &Color<T>) {
+        self.0 = self.0 + rhs.0;
+        self.1 = self.1 + rhs.1;
+        self.2 = self.2 + rhs.2;
     }
 }
 
-impl Sub for &Color {
-    type Output = Color;
+impl<T: Float> Sub for &Color<T> {
+    type Output = Color<T>;
 
     fn sub(self, rhs: Self) -> Self::Output {
         let red = self.0 - rhs.0;
         let green = self.1 - rhs.1;
         let blue = self.2 - rhs.2;
 
-        Color::new(red, green, blue)
+        Color(red, green, blue)
     }
 }
 
-impl Mul for &Color {
-    type Output = Color;
+impl<T: Float> SubAssign<&Color<T>> for Color<T> {
+    fn sub_assign(&mut self, rhs: &Color<T>) {
+        self.0 = self.0 - rhs.0;
+        self.1 = self.1 - rhs.1;
+        self.2 = self.2 - rhs.2;
+    }
+}
+
+impl<T: Float> Mul for &Color<T> {
+    type Output = Color<T>;
 
     fn mul(self, rhs: Self) -> Self::Output {
         let red = self.0 * rhs.0;
         let green = self.1 * rhs.1;
         let blue = self.2 * rhs.2;
 
-        Color::new(red, green, blue)
+        Color(red, green, blue)
     }
 }
 
-impl Mul<f32> for &Color {
-    type Output = Color;
+impl<T: Float> Mul<T> for &Color<T> {
+    type Output = Color<T>;
 
-    fn mul(self, rhs: f32) -> Self::Output {
+    fn mul(self, rhs: T) -> Self::Output {
         let red = self.0 * rhs;
         let green = self.1 * rhs;
         let blue = self.2 * rhs;
 
-        Color::new(red, green, blue)
+        Color(red, green, blue)
     }
 }
 
-impl Mul<&Color> for f32 {
-    type Output = Color;
+impl Mul<&Color<f32>> for f32 {
+    type Output = Color<f32>;
+
+    fn mul(self, rhs: &Color<f32>) -> Self::Output {
+        rhs * self
+    }
+}
 
-    fn mul(self, rhs: &Color) -> Self::Output {
-        let red = rhs.0 * self;
-        let green = rhs.1 * self;
-        let blue = rhs.2 * self;
+impl Mul<&Color<f64>> for f64 {
+    type Output = Color<f64>;
 
-        Color::new(red, green, blue)
+    fn mul(self, rhs: &Color<f64>) -> Self::Output {
+        rhs * self
     }
 }
 
 impl Display for Color {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> std::fmt::Result {
-        if let ColorFormat::RGB(red, green, blue) = self.get_rgb() {
+        if let ColorFormat::Rgb(red, green, blue) = self.get_rgb() {
             write!(f, "{} {} {}", red, green, blue)
         } else {
             Err(fmt::Error)
         }
     }
 }
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BitDepth {
+    Eight,
+    Sixteen,
+}
+
+impl BitDepth {
+    fn max_value(&self) -> u16 {
+        match self {
+            Self::Eight => u8::MAX as u16,
+            Self::Sixteen => u16::MAX,
+        }
+    }
+}
+
 pub struct Ppm(String);
 
 impl Ppm {
-    fn stringify(cv: &Canvas) -> Self {
+    fn stringify(cv: &Canvas, depth: BitDepth) -> Self {
         let mut ppm = String::new();
 
-        Self::header(&mut ppm, cv);
-        Self::serialize_colors(&mut ppm, cv);
+        Self::header(&mut ppm, cv, depth);
+        Self::serialize_colors(&mut ppm, cv, depth);
         Self::ends_with_new_line(&mut ppm);
 
         Self(ppm)
     }
 
-    fn header(buffer: &mut String, cv: &Canvas) {
-        let header = format!("P3\n{} {}\n{}\n", cv.width, cv.height, RGB);
+    fn header(buffer: &mut String, cv: &Canvas, depth: BitDepth) {
+        let header = format!("P3\n{} {}\n{}\n", cv.width, cv.height, depth.max_value());
         buffer.push_str(&header);
     }
 
-    fn serialize_colors(buffer: &mut String, cv: &Canvas) {
-        let mut counter = 0;
-        for c in cv {
-            let rgb = c.to_string();
-            let rgb_len = rgb.len();
+    fn serialize_color(c: &Color, depth: BitDepth) -> String {
+        let max = depth.max_value() as f32;
+        let scale = |channel: f32| (channel * max).clamp(0., max) as u16;
 
-            // prevent adding an overadded color and keep lines to 70 chars max
-            if counter > (cv.width * rgb_len) || (counter + rgb_len + 1) >= 69 {
-                buffer.push('\n');
-                counter = 0
-            }
+        format!("{} {} {}", scale(c.0), scale(c.1), scale(c.2))
+    }
 
-            if counter != 0 {
-                buffer.push(' ');
+    fn serialize_colors(buffer: &mut String, cv: &Canvas, depth: BitDepth) {
+        for row in cv.rows() {
+            let mut counter = 0;
+            for c in row {
+                let rgb = Self::serialize_color(c, depth);
+                let rgb_len = rgb.len();
+
+                // keep lines to 70 chars max without cutting a color in half
+                if counter != 0 && counter + rgb_len + 1 > 70 {
+                    buffer.push('\n');
+                    counter = 0
+                }
+
+                if counter != 0 {
+                    buffer.push(' ');
+                    counter += 1;
+                }
+
+                counter += rgb_len;
+                buffer.push_str(&rgb);
             }
-
-            counter += rgb_len + 1;
-            buffer.push_str(&rgb);
+            buffer.push('\n');
         }
     }
 
@@ -154,6 +287,14 @@ impl Ppm {
         }
     }
 
+    pub fn as_bytes(&self) -> &[u8] {
+        self.0.as_bytes()
+    }
+
+    pub fn into_string(self) -> String {
+        self.0
+    }
+
     pub fn write_to_file(&self, file: &str) {
         let mut f = File::create(file).expect("Unable to create file");
         f.write_all(self.0.as_bytes())
@@ -167,6 +308,154 @@ impl Display for Ppm {
     }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Pixel {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Pixel {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<(usize, usize)> for Pixel {
+    fn from((x, y): (usize, usize)) -> Self {
+        Self { x, y }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SizeError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for SizeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected {} bytes, found {}", self.expected, self.found)
+    }
+}
+
+impl Error for SizeError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct SizeMismatch {
+    pub expected: (usize, usize),
+    pub found: (usize, usize),
+}
+
+impl Display for SizeMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a {}x{} canvas, found {}x{}",
+            self.expected.0, self.expected.1, self.found.0, self.found.1
+        )
+    }
+}
+
+impl Error for SizeMismatch {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DivisibilityError {
+    pub factor: usize,
+    pub dimensions: (usize, usize),
+}
+
+impl Display for DivisibilityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{} canvas can't be divided by a factor of {}",
+            self.dimensions.0, self.dimensions.1, self.factor
+        )
+    }
+}
+
+impl Error for DivisibilityError {}
+
+#[derive(Debug)]
+pub enum PpmError {
+    BadMagic,
+    InvalidHeader,
+    InvalidData,
+    UnexpectedEof,
+    Io(io::Error),
+}
+
+impl Display for PpmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BadMagic => write!(f, "not a plain PPM, expected magic number P3"),
+            Self::InvalidHeader => write!(f, "invalid PPM header"),
+            Self::InvalidData => write!(f, "invalid PPM pixel data"),
+            Self::UnexpectedEof => write!(f, "unexpected end of PPM data"),
+            Self::Io(err) => write!(f, "unable to read PPM: {}", err),
+        }
+    }
+}
+
+impl Error for PpmError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for PpmError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    UnsupportedFormat(String),
+    Io(io::Error),
+    #[cfg(feature = "gif")]
+    Gif(GifError),
+}
+
+impl Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnsupportedFormat(ext) => write!(f, "unsupported image format '{}'", ext),
+            Self::Io(err) => write!(f, "unable to save canvas: {}", err),
+            #[cfg(feature = "gif")]
+            Self::Gif(err) => write!(f, "unable to save canvas: {}", err),
+        }
+    }
+}
+
+impl Error for SaveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            #[cfg(feature = "gif")]
+            Self::Gif(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "gif")]
+impl From<GifError> for SaveError {
+    fn from(err: GifError) -> Self {
+        Self::Gif(err)
+    }
+}
+
+#[derive(Clone)]
 pub struct Canvas {
     width: usize,
     height: usize,
@@ -189,6 +478,94 @@ impl Canvas {
         }
     }
 
+    pub fn from_rgb_bytes(width: usize, height: usize, bytes: &[u8]) -> Result<Self, SizeError> {
+        // no slice can hold usize::MAX bytes, so an overflowing size never matches
+        let expected = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .unwrap_or(usize::MAX);
+        if bytes.len() != expected {
+            return Err(SizeError {
+                expected,
+                found: bytes.len(),
+            });
+        }
+
+        let buffer = bytes
+            .chunks_exact(3)
+            .map(|rgb| {
+                Color::new(
+                    rgb[0] as f32 / RGB,
+                    rgb[1] as f32 / RGB,
+                    rgb[2] as f32 / RGB,
+                )
+            })
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            buffer,
+        })
+    }
+
+    pub fn from_ppm(ppm: &str) -> Result<Self, PpmError> {
+        let mut tokens = ppm
+            .lines()
+            .map(|l| l.split('#').next().unwrap_or_default())
+            .flat_map(str::split_whitespace);
+
+        if tokens.next().ok_or(PpmError::UnexpectedEof)? != "P3" {
+            return Err(PpmError::BadMagic);
+        }
+
+        let mut header = || -> Result<usize, PpmError> {
+            let token = tokens.next().ok_or(PpmError::UnexpectedEof)?;
+            token.parse().map_err(|_| PpmError::InvalidHeader)
+        };
+        let width = header()?;
+        let height = header()?;
+        let max_value = header()?;
+        if max_value == 0 || max_value > u16::MAX as usize {
+            return Err(PpmError::InvalidHeader);
+        }
+
+        let samples = width
+            .checked_mul(height)
+            .and_then(|pixels| pixels.checked_mul(3))
+            .ok_or(PpmError::InvalidHeader)?;
+
+        // make sure the data is all there before allocating for the header's size
+        let data: Vec<&str> = tokens.take(samples).collect();
+        if data.len() < samples {
+            return Err(PpmError::UnexpectedEof);
+        }
+
+        let mut values = Vec::with_capacity(samples);
+        for token in data {
+            let value: usize = token.parse().map_err(|_| PpmError::InvalidData)?;
+            if value > max_value {
+                return Err(PpmError::InvalidData);
+            }
+            values.push(value as f32 / max_value as f32);
+        }
+
+        let buffer = values
+            .chunks_exact(3)
+            .map(|c| Color::new(c[0], c[1], c[2]))
+            .collect();
+
+        Ok(Self {
+            width,
+            height,
+            buffer,
+        })
+    }
+
+    pub fn read_ppm(path: &str) -> Result<Self, PpmError> {
+        Self::from_ppm(&fs::read_to_string(path)?)
+    }
+
     pub fn get_height(&self) -> usize {
         self.height
     }
@@ -197,10 +574,18 @@ impl Canvas {
         self.width
     }
 
-    fn len(&self) -> usize {
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    pub fn pixel_count(&self) -> usize {
         self.height * self.width
     }
 
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
     fn get_index(&self, x: usize, y: usize) -> usize {
         x + self.width * y
     }
@@ -213,16 +598,379 @@ impl Canvas {
         }
     }
 
+    pub fn write_pixels(&mut self, pixels: &[(usize, usize, Color)]) {
+        for (x, y, color) in pixels {
+            if self.contains(*x, *y) {
+                self.write_pixel(*x, *y, color);
+            }
+        }
+    }
+
+    pub fn write_pixel_at(&mut self, px: Pixel, color: &Color) {
+        if self.contains(px.x, px.y) {
+            self.write_pixel(px.x, px.y, color)
+        }
+    }
+
+    pub fn add_pixel(&mut self, x: usize, y: usize, color: &Color) {
+        if !self.contains(x, y) {
+            return;
+        }
+
+        let idx = self.get_index(x, y);
+        self.buffer[idx] += color;
+    }
+
     pub fn pixel_at(&self, x: usize, y: usize) -> &Color {
         let idx = self.get_index(x, y);
         &self.buffer[idx]
     }
 
+    pub fn rows(&self) -> impl Iterator<Item = &[Color]> {
+        self.buffer.chunks(self.width.max(1))
+    }
+
+    // a step of 0 samples every pixel, like a step of 1
+    pub fn sample_every(&self, step: usize) -> impl Iterator<Item = (usize, usize, &Color)> {
+        let step = step.max(1);
+        (0..self.height).step_by(step).flat_map(move |y| {
+            (0..self.width)
+                .step_by(step)
+                .map(move |x| (x, y, self.pixel_at(x, y)))
+        })
+    }
+
+    pub fn pixel(&self, px: Pixel) -> &Color {
+        self.pixel_at(px.x, px.y)
+    }
+
     pub fn to_ppm(&self) -> Ppm {
-        Ppm::stringify(self)
+        self.to_ppm_with_depth(BitDepth::Eight)
+    }
+
+    pub fn to_ppm_with_depth(&self, depth: BitDepth) -> Ppm {
+        Ppm::stringify(self, depth)
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), SaveError> {
+        let ext = Path::new(path)
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+
+        match ext.as_str() {
+            "ppm" => Ok(fs::write(path, self.to_ppm().as_bytes())?),
+            #[cfg(feature = "gif")]
+            "gif" => Ok(write_gif(path, std::slice::from_ref(self), 0)?),
+            _ => Err(SaveError::UnsupportedFormat(ext)),
+        }
+    }
+
+    pub fn as_rgb_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.pixel_count() * 3);
+        for c in self {
+            if let ColorFormat::Rgb(red, green, blue) = c.get_rgb() {
+                bytes.extend([red, green, blue]);
+            }
+        }
+        bytes
+    }
+
+    pub fn as_rgba_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.pixel_count() * 4);
+        for rgb in self.as_rgb_bytes().chunks_exact(3) {
+            bytes.extend_from_slice(rgb);
+            bytes.push(u8::MAX);
+        }
+        bytes
+    }
+
+    pub fn map(&self, f: impl Fn(&Color) -> Color) -> Canvas {
+        Canvas {
+            width: self.width,
+            height: self.height,
+            buffer: self.buffer.iter().map(f).collect(),
+        }
+    }
+
+    pub fn map_in_place(&mut self, f: impl Fn(&Color) -> Color) {
+        for px in self.buffer.iter_mut() {
+            *px = f(px);
+        }
+    }
+
+    pub fn tint(&self, color: &Color) -> Canvas {
+        self.map(|c| c * color)
+    }
+
+    pub fn grayscale(&self) -> Canvas {
+        self.map(|c| {
+            let l = c.luminance();
+            Color::new(l, l, l)
+        })
+    }
+
+    pub fn blur_gaussian(&self, radius: f32) -> Canvas {
+        if radius <= 0. {
+            return self.clone();
+        }
+
+        let half = (3. * radius).ceil() as isize;
+        let weights: Vec<f32> = (-half..=half)
+            .map(|i| (-(i * i) as f32 / (2. * radius * radius)).exp())
+            .collect();
+        let total: f32 = weights.iter().sum();
+        let kernel: Vec<(isize, f32)> = (-half..=half)
+            .zip(weights.iter().map(|w| w / total))
+            .collect();
+
+        self.blur_pass(&kernel, true).blur_pass(&kernel, false)
+    }
+
+    fn blur_pass(&self, kernel: &[(isize, f32)], horizontal: bool) -> Canvas {
+        let mut blurred = Canvas::new(self.width, self.height, None);
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                let mut acc = Color::new(0., 0., 0.);
+                for (offset, weight) in kernel {
+                    // pixels outside the canvas repeat the closest edge pixel
+                    let (sx, sy) = if horizontal {
+                        (
+                            (x as isize + offset).clamp(0, self.width as isize - 1) as usize,
+                            y,
+                        )
+                    } else {
+                        (
+                            x,
+                            (y as isize + offset).clamp(0, self.height as isize - 1) as usize,
+                        )
+                    };
+                    acc = &acc + &(self.pixel_at(sx, sy) * *weight);
+                }
+                blurred.write_pixel(x, y, &acc);
+            }
+        }
+        blurred
+    }
+
+    pub fn bloom(&self, threshold: f32, intensity: f32) -> Canvas {
+        let mut highlights = Canvas::new(self.width, self.height, None);
+        for (px, c) in highlights.buffer.iter_mut().zip(&self.buffer) {
+            if c.luminance() > threshold {
+                *px = c.clone();
+            }
+        }
+
+        let glow = highlights.blur_gaussian(BLOOM_RADIUS);
+        let buffer = self
+            .buffer
+            .iter()
+            .zip(&glow.buffer)
+            .map(|(c, g)| c + &(g * intensity))
+            .collect();
+
+        Canvas {
+            width: self.width,
+            height: self.height,
+            buffer,
+        }
+    }
+
+    pub fn downsample(&self, factor: usize) -> Result<Canvas, DivisibilityError> {
+        if factor == 0 || !self.width.is_multiple_of(factor) || !self.height.is_multiple_of(factor)
+        {
+            return Err(DivisibilityError {
+                factor,
+                dimensions: self.dimensions(),
+            });
+        }
+
+        let mut small = Canvas::new(self.width / factor, self.height / factor, None);
+        let weight = 1. / (factor * factor) as f32;
+        for y in 0..self.height {
+            for x in 0..self.width {
+                small.add_pixel(x / factor, y / factor, &(self.pixel_at(x, y) * weight));
+            }
+        }
+
+        Ok(small)
+    }
+
+    pub fn diff(&self, other: &Canvas) -> Result<f64, SizeMismatch> {
+        if self.dimensions() != other.dimensions() {
+            return Err(SizeMismatch {
+                expected: self.dimensions(),
+                found: other.dimensions(),
+            });
+        }
+
+        if self.pixel_count() == 0 {
+            return Ok(0.);
+        }
+
+        let mut total = 0.;
+        for (a, b) in self.buffer.iter().zip(&other.buffer) {
+            total += (a.0 - b.0).abs() as f64 + (a.1 - b.1).abs() as f64 + (a.2 - b.2).abs() as f64;
+        }
+
+        Ok(total / (self.pixel_count() * 3) as f64)
+    }
+
+    pub fn diff_mask(&self, other: &Canvas, threshold: f32) -> Result<Canvas, SizeMismatch> {
+        if self.dimensions() != other.dimensions() {
+            return Err(SizeMismatch {
+                expected: self.dimensions(),
+                found: other.dimensions(),
+            });
+        }
+
+        let red = Color::new(1., 0., 0.);
+        let mut mask = Canvas::new(self.width, self.height, None);
+        for (px, (a, b)) in mask
+            .buffer
+            .iter_mut()
+            .zip(self.buffer.iter().zip(&other.buffer))
+        {
+            let delta = (a.0 - b.0)
+                .abs()
+                .max((a.1 - b.1).abs())
+                .max((a.2 - b.2).abs());
+            if delta > threshold {
+                *px = red.clone();
+            }
+        }
+
+        Ok(mask)
+    }
+
+    pub fn to_ascii(&self, cols: usize) -> String {
+        if self.pixel_count() == 0 {
+            return String::new();
+        }
+
+        let cols = cols.clamp(1, self.width);
+        // terminal characters are roughly twice as tall as they are wide
+        let rows = (self.height * cols / self.width / 2).max(1);
+
+        let mut ascii = String::with_capacity((cols + 1) * rows);
+        for row in 0..rows {
+            let (y0, y1) = Self::block_bounds(row, rows, self.height);
+            for col in 0..cols {
+                let (x0, x1) = Self::block_bounds(col, cols, self.width);
+
+                let mut total = 0.;
+                for y in y0..y1 {
+                    for x in x0..x1 {
+                        total += self.pixel_at(x, y).luminance();
+                    }
+                }
+                let luminance = (total / ((x1 - x0) * (y1 - y0)) as f32).clamp(0., 1.);
+
+                let idx = (luminance * (ASCII_RAMP.len() - 1) as f32).round() as usize;
+                ascii.push(ASCII_RAMP[idx] as char);
+            }
+            ascii.push('\n');
+        }
+        ascii
+    }
+
+    fn block_bounds(block: usize, blocks: usize, size: usize) -> (usize, usize) {
+        let start = block * size / blocks;
+        let end = ((block + 1) * size / blocks).max(start + 1);
+        (start, end)
+    }
+}
+
+#[cfg(feature = "gif")]
+#[derive(Debug)]
+pub enum GifError {
+    NoFrames,
+    TooLarge((usize, usize)),
+    SizeMismatch(SizeMismatch),
+    Io(io::Error),
+    Encoding(gif::EncodingError),
+}
+
+#[cfg(feature = "gif")]
+impl Display for GifError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoFrames => write!(f, "a gif needs at least one frame"),
+            Self::TooLarge((width, height)) => write!(
+                f,
+                "{}x{} frame is too large for a gif, at most {} pixels per side",
+                width,
+                height,
+                u16::MAX
+            ),
+            Self::SizeMismatch(err) => write!(f, "gif frames differ in size: {}", err),
+            Self::Io(err) => write!(f, "unable to write gif: {}", err),
+            Self::Encoding(err) => write!(f, "unable to encode gif: {}", err),
+        }
+    }
+}
+
+#[cfg(feature = "gif")]
+impl Error for GifError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::SizeMismatch(err) => Some(err),
+            Self::Io(err) => Some(err),
+            Self::Encoding(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "gif")]
+impl From<io::Error> for GifError {
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
+
+#[cfg(feature = "gif")]
+impl From<gif::EncodingError> for GifError {
+    fn from(err: gif::EncodingError) -> Self {
+        Self::Encoding(err)
     }
 }
 
+#[cfg(feature = "gif")]
+pub fn write_gif(path: &str, frames: &[Canvas], delay_ms: u16) -> Result<(), GifError> {
+    let Some(first) = frames.first() else {
+        return Err(GifError::NoFrames);
+    };
+
+    let (width, height) = first.dimensions();
+    let (Ok(gif_width), Ok(gif_height)) = (u16::try_from(width), u16::try_from(height)) else {
+        return Err(GifError::TooLarge((width, height)));
+    };
+    if let Some(other) = frames.iter().find(|f| f.dimensions() != (width, height)) {
+        return Err(GifError::SizeMismatch(SizeMismatch {
+            expected: (width, height),
+            found: other.dimensions(),
+        }));
+    }
+
+    let f = File::create(path)?;
+    let mut encoder = gif::Encoder::new(f, gif_width, gif_height, &[])?;
+    encoder.set_repeat(gif::Repeat::Infinite)?;
+
+    for canvas in frames {
+        let mut rgba = canvas.as_rgba_bytes();
+        // each frame gets its own palette quantized from its pixels
+        let mut frame = gif::Frame::from_rgba_speed(gif_width, gif_height, &mut rgba, 10);
+        // gif delays are expressed in hundredths of a second
+        frame.delay = delay_ms / 10;
+        encoder.write_frame(&frame)?;
+    }
+
+    Ok(())
+}
+
 pub struct CanvasIter<'a> {
     inner: &'a Canvas,
     index: usize,
@@ -242,7 +990,7 @@ impl<'a> Iterator for CanvasIter<'a> {
 
     fn next(&mut self) -> Option<Self::Item> {
         match self.index {
-            idx if idx < self.inner.len() => {
+            idx if idx < self.inner.pixel_count() => {
                 let color = &self.inner.buffer[idx];
                 self.index += 1;
                 Some(color)
@@ -264,21 +1012,26 @@ impl<'a> IntoIterator for &'a Canvas {
 
 #[cfg(test)]
 mod test {
-    use std::f32::EPSILON;
-
     use float_eq::assert_float_eq;
 
     use crate::canvas::Color;
 
-    use super::Canvas;
+    use std::{
+        collections::{hash_map::DefaultHasher, HashSet},
+        hash::{Hash, Hasher},
+    };
+
+    use super::{
+        BitDepth, Canvas, DivisibilityError, Pixel, PpmError, SaveError, SizeError, SizeMismatch,
+    };
 
     #[test]
     fn check_color_elements() {
-        let c = Color(-0.5, 0.4, 1.7);
+        let c: Color = Color(-0.5, 0.4, 1.7);
 
-        assert_float_eq!(c.0, -0.5, abs <= EPSILON);
-        assert_float_eq!(c.1, 0.4, abs <= EPSILON);
-        assert_float_eq!(c.2, 1.7, abs <= EPSILON);
+        assert_float_eq!(c.0, -0.5, abs <= f32::EPSILON);
+        assert_float_eq!(c.1, 0.4, abs <= f32::EPSILON);
+        assert_float_eq!(c.2, 1.7, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -290,9 +1043,9 @@ mod test {
 
         let ref_c = Color::new(1.6, 0.7, 1.);
 
-        assert_float_eq!(c3.0, ref_c.0, abs <= EPSILON);
-        assert_float_eq!(c3.1, ref_c.1, abs <= EPSILON);
-        assert_float_eq!(c3.2, ref_c.2, abs <= EPSILON);
+        assert_float_eq!(c3.0, ref_c.0, abs <= f32::EPSILON);
+        assert_float_eq!(c3.1, ref_c.1, abs <= f32::EPSILON);
+        assert_float_eq!(c3.2, ref_c.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -304,9 +1057,33 @@ mod test {
 
         let ref_c = Color::new(0.2, 0.5, 0.5);
 
-        assert_float_eq!(c3.0, ref_c.0, abs <= EPSILON);
-        assert_float_eq!(c3.1, ref_c.1, abs <= EPSILON);
-        assert_float_eq!(c3.2, ref_c.2, abs <= EPSILON);
+        assert_float_eq!(c3.0, ref_c.0, abs <= f32::EPSILON);
+        assert_float_eq!(c3.1, ref_c.1, abs <= f32::EPSILON);
+        assert_float_eq!(c3.2, ref_c.2, abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn accumulates_colors() {
+        let contributions = [
+            Color::new(0.1, 0.2, 0.3),
+            Color::new(0.4, 0., 0.25),
+            Color::new(0.5, 0.3, 0.25),
+        ];
+        let mut total = Color::new(0., 0., 0.);
+
+        for c in &contributions {
+            total += c;
+        }
+
+        assert_float_eq!(total.0, 1., abs <= 1e-6);
+        assert_float_eq!(total.1, 0.5, abs <= 1e-6);
+        assert_float_eq!(total.2, 0.8, abs <= 1e-6);
+
+        total -= &contributions[0];
+
+        assert_float_eq!(total.0, 0.9, abs <= 1e-6);
+        assert_float_eq!(total.1, 0.3, abs <= 1e-6);
+        assert_float_eq!(total.2, 0.5, abs <= 1e-6);
     }
 
     #[test]
@@ -318,9 +1095,22 @@ mod test {
 
         let ref_c = Color::new(0.4, 0.6, 0.8);
 
-        assert_float_eq!(c2.0, ref_c.0, abs <= EPSILON);
-        assert_float_eq!(c2.1, ref_c.1, abs <= EPSILON);
-        assert_float_eq!(c2.2, ref_c.2, abs <= EPSILON);
+        assert_float_eq!(c2.0, ref_c.0, abs <= f32::EPSILON);
+        assert_float_eq!(c2.1, ref_c.1, abs <= f32::EPSILON);
+        assert_float_eq!(c2.2, ref_c.2, abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn add_double_precision_colors() {
+        let c1 = Color::<f64>::from_channels(0.9, 0.6, 1e-10);
+        let c2 = Color::<f64>::from_channels(0.7, 0.1, 1.);
+
+        let c3 = &c1 + &c2;
+
+        assert_float_eq!(c3.0, 1.6, abs <= 1e-12);
+        assert_float_eq!(c3.1, 0.7, abs <= 1e-12);
+        assert!(c3.2 > 1.);
+        assert!(c3 == Color::from_channels(0.9 + 0.7, 0.6 + 0.1, 1e-10 + 1.));
     }
 
     #[test]
@@ -332,9 +1122,69 @@ mod test {
 
         let ref_c = Color::new(0.9, 0.2, 0.04);
 
-        assert_float_eq!(c3.0, ref_c.0, abs <= EPSILON);
-        assert_float_eq!(c3.1, ref_c.1, abs <= EPSILON);
-        assert_float_eq!(c3.2, ref_c.2, abs <= EPSILON);
+        assert_float_eq!(c3.0, ref_c.0, abs <= f32::EPSILON);
+        assert_float_eq!(c3.1, ref_c.1, abs <= f32::EPSILON);
+        assert_float_eq!(c3.2, ref_c.2, abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn green_is_brighter_than_blue() {
+        let green = Color::new(0., 1., 0.);
+        let blue = Color::new(0., 0., 1.);
+
+        assert!(green.luminance() > blue.luminance());
+    }
+
+    #[test]
+    fn white_luminance_is_one() {
+        let white = Color::new(1., 1., 1.);
+
+        assert_float_eq!(white.luminance(), 1., abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn daylight_temperature_is_near_white() {
+        let c = Color::from_kelvin(6500.);
+
+        assert!(c.0 > 0.95 && c.1 > 0.95 && c.2 > 0.95);
+    }
+
+    #[test]
+    fn low_temperature_is_reddish() {
+        let c = Color::from_kelvin(2000.);
+
+        assert!(c.0 > c.1 && c.1 > c.2);
+        assert!(c.2 < 0.2);
+    }
+
+    #[test]
+    fn srgb_round_trip_preserves_color() {
+        let gray = Color::new(0.5, 0.5, 0.5);
+
+        let c = gray.srgb_to_linear().linear_to_srgb();
+
+        assert!(gray.srgb_to_linear().0 < 0.5);
+        assert_float_eq!(c.0, 0.5, abs <= 1e-5);
+        assert_float_eq!(c.1, 0.5, abs <= 1e-5);
+        assert_float_eq!(c.2, 0.5, abs <= 1e-5);
+    }
+
+    #[test]
+    fn srgb_conversion_keeps_black_and_white() {
+        let c = Color::new(0., 1., 0.);
+
+        for converted in [c.srgb_to_linear(), c.linear_to_srgb()] {
+            assert_float_eq!(converted.0, 0., abs <= 1e-6);
+            assert_float_eq!(converted.1, 1., abs <= 1e-6);
+            assert_float_eq!(converted.2, 0., abs <= 1e-6);
+        }
+    }
+
+    #[test]
+    fn clamps_color_channels() {
+        let c = Color::new(1.5, -0.2, 0.5);
+
+        assert!(c.clamped() == Color::new(1., 0., 0.5));
     }
 
     #[test]
@@ -343,15 +1193,23 @@ mod test {
 
         assert_eq!(canv.width, 10);
         assert_eq!(canv.height, 20);
-        assert_eq!(canv.len(), 20 * 10);
+        assert_eq!(canv.pixel_count(), 20 * 10);
 
         for c in &canv {
-            assert_float_eq!(c.0, 0., abs <= EPSILON);
-            assert_float_eq!(c.1, 0., abs <= EPSILON);
-            assert_float_eq!(c.2, 0., abs <= EPSILON);
+            assert_float_eq!(c.0, 0., abs <= f32::EPSILON);
+            assert_float_eq!(c.1, 0., abs <= f32::EPSILON);
+            assert_float_eq!(c.2, 0., abs <= f32::EPSILON);
         }
     }
 
+    #[test]
+    fn canvas_dimensions_match_getters() {
+        let canv = Canvas::new(10, 20, None);
+
+        assert_eq!(canv.dimensions(), (canv.get_width(), canv.get_height()));
+        assert_eq!(canv.pixel_count(), canv.get_width() * canv.get_height());
+    }
+
     #[test]
     fn writes_pixels_to_canvas() {
         let mut canv = Canvas::new(10, 20, None);
@@ -360,9 +1218,107 @@ mod test {
         canv.write_pixel(2, 3, &red);
         let c = canv.pixel_at(2, 3);
 
-        assert_float_eq!(c.0, red.0, abs <= EPSILON);
-        assert_float_eq!(c.1, red.1, abs <= EPSILON);
-        assert_float_eq!(c.2, red.2, abs <= EPSILON);
+        assert_float_eq!(c.0, red.0, abs <= f32::EPSILON);
+        assert_float_eq!(c.1, red.1, abs <= f32::EPSILON);
+        assert_float_eq!(c.2, red.2, abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn checks_whether_canvas_contains_a_point() {
+        let canv = Canvas::new(10, 20, None);
+
+        assert!(canv.contains(9, 19));
+        assert!(!canv.contains(10, 0));
+        assert!(!canv.contains(0, 20));
+    }
+
+    #[test]
+    fn writes_a_batch_of_pixels() {
+        let mut canv = Canvas::new(4, 3, None);
+        let red = Color::new(1., 0., 0.);
+        let black = Color::new(0., 0., 0.);
+
+        canv.write_pixels(&[
+            (0, 0, red.clone()),
+            (3, 1, red.clone()),
+            (2, 2, red.clone()),
+            (4, 0, red.clone()),
+        ]);
+
+        for y in 0..3 {
+            for x in 0..4 {
+                let written = matches!((x, y), (0, 0) | (3, 1) | (2, 2));
+                let expected = if written { &red } else { &black };
+                assert!(canv.pixel_at(x, y) == expected);
+            }
+        }
+    }
+
+    #[test]
+    fn writes_pixels_by_coordinate() {
+        let mut canv = Canvas::new(10, 20, None);
+        let red = Color::new(1., 0., 0.);
+        let px = Pixel::new(2, 3);
+
+        canv.write_pixel_at(px, &red);
+
+        assert!(*canv.pixel(px) == red);
+        assert!(*canv.pixel_at(2, 3) == red);
+    }
+
+    #[test]
+    fn identical_pixels_hash_equal() {
+        let hash = |px: &Pixel| {
+            let mut hasher = DefaultHasher::new();
+            px.hash(&mut hasher);
+            hasher.finish()
+        };
+        let px1 = Pixel::new(4, 7);
+        let px2 = Pixel::from((4, 7));
+
+        assert_eq!(hash(&px1), hash(&px2));
+
+        let dirty: HashSet<Pixel> = [px1, px2, Pixel::new(7, 4)].into_iter().collect();
+        assert_eq!(dirty.len(), 2);
+    }
+
+    #[test]
+    fn adds_color_to_existing_pixel() {
+        let mut canv = Canvas::new(1, 1, None);
+        let red = Color::new(1., 0., 0.);
+
+        canv.add_pixel(0, 0, &red);
+        canv.add_pixel(0, 0, &red);
+
+        assert!(*canv.pixel_at(0, 0) == Color::new(2., 0., 0.));
+
+        let ppm = canv.to_ppm().to_string();
+        assert_eq!(ppm.lines().nth(3), Some("255 0 0"));
+    }
+
+    #[test]
+    fn pixels_past_the_edge_do_not_wrap() {
+        let mut canv = Canvas::new(3, 2, None);
+        let red = Color::new(1., 0., 0.);
+
+        canv.add_pixel(3, 0, &red);
+        canv.write_pixel_at(Pixel::new(3, 0), &red);
+        canv.add_pixel(0, 2, &red);
+
+        assert!(canv.into_iter().all(|c| *c == Color::new(0., 0., 0.)));
+    }
+
+    #[test]
+    fn iterates_canvas_rows() {
+        let mut canv = Canvas::new(3, 2, None);
+        let red = Color::new(1., 0., 0.);
+        canv.write_pixel(1, 1, &red);
+
+        let rows: Vec<&[Color]> = canv.rows().collect();
+
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.len() == 3));
+        assert!(rows[1][1] == red);
     }
 
     #[test]
@@ -430,6 +1386,36 @@ mod test {
         );
     }
 
+    #[test]
+    fn ppm_bytes_match_its_string() {
+        let canv = Canvas::new(5, 3, Some(Color::new(0.2, 0.4, 0.6)));
+        let ppm = canv.to_ppm();
+
+        assert_eq!(ppm.as_bytes(), ppm.to_string().as_bytes());
+        assert_eq!(canv.to_ppm().into_string(), ppm.to_string());
+    }
+
+    #[test]
+    fn exports_16_bit_ppm() {
+        let canv = Canvas::new(1, 1, Some(Color::new(0.5, 1., 1.5)));
+
+        let ppm = canv.to_ppm_with_depth(BitDepth::Sixteen).to_string();
+
+        let mut ppm_lines = ppm.lines().skip(2);
+        assert_eq!(ppm_lines.next(), Some("65535"));
+        assert_eq!(ppm_lines.next(), Some("32767 65535 65535"));
+    }
+
+    #[test]
+    fn ppm_16_bit_round_trip() {
+        let canv = Canvas::new(2, 1, Some(Color::new(0.25, 0.5, 1.)));
+
+        let ppm = canv.to_ppm_with_depth(BitDepth::Sixteen).to_string();
+        let read = Canvas::from_ppm(&ppm).unwrap();
+
+        assert!(read.diff(&canv).unwrap() < 1e-4);
+    }
+
     #[test]
     fn ppm_ends_with_new_line() {
         let canv = Canvas::new(5, 3, None);
@@ -438,4 +1424,451 @@ mod test {
 
         assert!(ppm.ends_with('\n'));
     }
+
+    #[test]
+    fn white_canvas_ascii_preview_uses_densest_character() {
+        let canv = Canvas::new(20, 10, Some(Color::new(1., 1., 1.)));
+
+        let ascii = canv.to_ascii(10);
+
+        assert!(ascii
+            .lines()
+            .all(|l| l.len() == 10 && l.chars().all(|c| c == '@')));
+    }
+
+    #[test]
+    fn black_canvas_ascii_preview_uses_lightest_character() {
+        let canv = Canvas::new(20, 10, None);
+
+        let ascii = canv.to_ascii(10);
+
+        assert!(ascii
+            .lines()
+            .all(|l| l.len() == 10 && l.chars().all(|c| c == ' ')));
+    }
+
+    #[test]
+    fn maps_every_pixel() {
+        let canv = Canvas::new(3, 2, Some(Color::new(0.2, 0.4, 0.8)));
+
+        let mapped = canv.map(|c| c * 0.5);
+
+        for c in &mapped {
+            assert!(*c == Color::new(0.1, 0.2, 0.4));
+        }
+    }
+
+    #[test]
+    fn maps_every_pixel_in_place() {
+        let mut canv = Canvas::new(3, 2, Some(Color::new(0.2, 0.4, 0.8)));
+
+        canv.map_in_place(|c| c * 0.5);
+
+        for c in &canv {
+            assert!(*c == Color::new(0.1, 0.2, 0.4));
+        }
+    }
+
+    #[test]
+    fn tints_every_pixel() {
+        let canv = Canvas::new(3, 2, Some(Color::new(1., 1., 1.)));
+
+        let tinted = canv.tint(&Color::new(1., 0.5, 0.5));
+
+        for c in &tinted {
+            assert!(*c == Color::new(1., 0.5, 0.5));
+        }
+    }
+
+    #[test]
+    fn grayscale_canvas_uses_luminance() {
+        let canv = Canvas::new(4, 3, Some(Color::new(1., 0., 0.)));
+
+        let gray = canv.grayscale();
+
+        for c in &gray {
+            assert_float_eq!(c.0, 0.2126, abs <= f32::EPSILON);
+            assert_float_eq!(c.0, c.1, abs <= f32::EPSILON);
+            assert_float_eq!(c.1, c.2, abs <= f32::EPSILON);
+        }
+    }
+
+    #[test]
+    fn white_canvas_as_rgb_bytes() {
+        let canv = Canvas::new(1, 1, Some(Color::new(1., 1., 1.)));
+
+        assert_eq!(canv.as_rgb_bytes(), vec![255, 255, 255]);
+    }
+
+    #[test]
+    fn rgb_bytes_are_clamped_and_row_major() {
+        let mut canv = Canvas::new(2, 1, None);
+        canv.write_pixel(1, 0, &Color::new(1.5, -0.5, 0.5));
+
+        assert_eq!(canv.as_rgb_bytes(), vec![0, 0, 0, 255, 0, 127]);
+    }
+
+    #[test]
+    fn red_canvas_as_rgba_bytes() {
+        let canv = Canvas::new(1, 1, Some(Color::new(1., 0., 0.)));
+
+        assert_eq!(canv.as_rgba_bytes(), vec![255, 0, 0, 255]);
+    }
+
+    #[test]
+    fn creates_canvas_from_rgb_bytes() {
+        let canv = Canvas::from_rgb_bytes(1, 1, &[255, 0, 0]).unwrap();
+
+        let c = canv.pixel_at(0, 0);
+
+        assert_float_eq!(c.0, 1., abs <= f32::EPSILON);
+        assert_float_eq!(c.1, 0., abs <= f32::EPSILON);
+        assert_float_eq!(c.2, 0., abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn rgb_bytes_round_trip_through_canvas() {
+        let bytes = [0, 51, 102, 153, 204, 255];
+
+        let canv = Canvas::from_rgb_bytes(2, 1, &bytes).unwrap();
+
+        assert_eq!(canv.as_rgb_bytes(), bytes);
+    }
+
+    #[test]
+    fn fails_to_create_canvas_from_wrongly_sized_bytes() {
+        let res = Canvas::from_rgb_bytes(2, 2, &[255, 0, 0]);
+
+        assert_eq!(
+            res.err(),
+            Some(SizeError {
+                expected: 12,
+                found: 3
+            })
+        );
+    }
+
+    #[test]
+    fn fails_to_create_canvas_with_overflowing_size() {
+        let res = Canvas::from_rgb_bytes(usize::MAX, 2, &[]);
+
+        assert_eq!(
+            res.err(),
+            Some(SizeError {
+                expected: usize::MAX,
+                found: 0
+            })
+        );
+    }
+
+    #[test]
+    fn reads_ppm_scaled_by_max_value() {
+        let ppm = "P3\n2 2\n15\n15 0 0  0 15 0\n0 0 15  3 6 9\n";
+
+        let canv = Canvas::from_ppm(ppm).unwrap();
+
+        assert_eq!(canv.get_width(), 2);
+        assert_eq!(canv.get_height(), 2);
+        assert!(*canv.pixel_at(0, 0) == Color::new(1., 0., 0.));
+        assert!(*canv.pixel_at(1, 0) == Color::new(0., 1., 0.));
+        assert!(*canv.pixel_at(0, 1) == Color::new(0., 0., 1.));
+        assert!(*canv.pixel_at(1, 1) == Color::new(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn reading_ppm_ignores_comments() {
+        let ppm = "P3\n# a comment\n1 1\n255 # max value\n255 0 255\n";
+
+        let canv = Canvas::from_ppm(ppm).unwrap();
+
+        assert!(*canv.pixel_at(0, 0) == Color::new(1., 0., 1.));
+    }
+
+    #[test]
+    fn reading_ppm_with_unknown_magic_is_an_error() {
+        let path = std::env::temp_dir().join("ray_tracer_challenge_p9.ppm");
+        let path = path.to_str().unwrap();
+        std::fs::write(path, "P9\n1 1\n255\n255 0 255\n").unwrap();
+
+        assert!(matches!(Canvas::read_ppm(path), Err(PpmError::BadMagic)));
+    }
+
+    #[test]
+    fn samples_every_other_pixel() {
+        let canv = Canvas::new(10, 10, Some(Color::new(0.5, 0.5, 0.5)));
+
+        let samples: Vec<_> = canv.sample_every(2).collect();
+
+        assert_eq!(samples.len(), 25);
+        assert!(samples.iter().all(|(x, y, _)| x % 2 == 0 && y % 2 == 0));
+        assert_eq!((samples[1].0, samples[1].1), (2, 0));
+        assert_eq!((samples[5].0, samples[5].1), (0, 2));
+    }
+
+    #[test]
+    fn sampling_with_zero_step_visits_every_pixel() {
+        let canv = Canvas::new(3, 2, None);
+
+        assert_eq!(canv.sample_every(0).count(), 6);
+        assert_eq!(canv.sample_every(1).count(), 6);
+    }
+
+    #[test]
+    fn saves_ppm_by_extension() {
+        let canv = Canvas::new(2, 2, Some(Color::new(1., 0.5, 0.)));
+        let path = std::env::temp_dir().join("ray_tracer_challenge_out.ppm");
+        let path = path.to_str().unwrap();
+
+        canv.save(path).unwrap();
+
+        let ppm = std::fs::read_to_string(path).unwrap();
+        assert!(ppm.starts_with("P3\n2 2\n255\n"));
+        assert!(Canvas::from_ppm(&ppm).unwrap().diff(&canv).unwrap() < 1e-2);
+    }
+
+    #[test]
+    fn saving_unknown_extension_is_an_error() {
+        let canv = Canvas::new(2, 2, None);
+
+        assert!(matches!(
+            canv.save("out.xyz"),
+            Err(SaveError::UnsupportedFormat(ext)) if ext == "xyz"
+        ));
+        assert!(matches!(
+            canv.save("out"),
+            Err(SaveError::UnsupportedFormat(_))
+        ));
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn saves_single_frame_gif_by_extension() {
+        let canv = Canvas::new(4, 3, Some(Color::new(0., 1., 0.)));
+        let path = std::env::temp_dir().join("ray_tracer_challenge_out.gif");
+        let path = path.to_str().unwrap();
+
+        canv.save(path).unwrap();
+
+        let bytes = std::fs::read(path).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+    }
+
+    #[test]
+    fn reading_ppm_with_overflowing_dimensions_is_an_error() {
+        assert!(matches!(
+            Canvas::from_ppm("P3\n18446744073709551615 2\n255\n"),
+            Err(PpmError::InvalidHeader)
+        ));
+        assert!(matches!(
+            Canvas::from_ppm("P3\n6148914691236517206 1\n255\n"),
+            Err(PpmError::InvalidHeader)
+        ));
+    }
+
+    #[test]
+    fn reading_ppm_with_out_of_range_values_is_an_error() {
+        for ppm in [
+            "P3\n1 1\n0\n0 0 0\n",
+            "P3\n1 1\n65536\n0 0 0\n",
+            "P3\n1 1\n255.5\n0 0 0\n",
+        ] {
+            assert!(matches!(
+                Canvas::from_ppm(ppm),
+                Err(PpmError::InvalidHeader)
+            ));
+        }
+
+        for ppm in [
+            "P3\n1 1\n255\nnan inf -3\n",
+            "P3\n1 1\n15\n300 0 0\n",
+            "P3\n1 1\n255\n0.5 0 0\n",
+        ] {
+            assert!(matches!(Canvas::from_ppm(ppm), Err(PpmError::InvalidData)));
+        }
+    }
+
+    #[test]
+    fn reading_truncated_ppm_is_an_error() {
+        assert!(matches!(
+            Canvas::from_ppm("P3\n2 1\n255\n255 0 255\n"),
+            Err(PpmError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            Canvas::from_ppm("P3\n2 x\n255\n"),
+            Err(PpmError::InvalidHeader)
+        ));
+        assert!(matches!(
+            Canvas::from_ppm("P3\n100000 100000\n255\n0 0 0\n"),
+            Err(PpmError::UnexpectedEof)
+        ));
+        assert!(matches!(
+            Canvas::read_ppm("/nonexistent/image.ppm"),
+            Err(PpmError::Io(_))
+        ));
+    }
+
+    #[test]
+    fn gaussian_blur_spreads_a_bright_pixel() {
+        let mut canv = Canvas::new(9, 9, None);
+        canv.write_pixel(4, 4, &Color::new(1., 1., 1.));
+
+        let blurred = canv.blur_gaussian(1.);
+
+        assert!(blurred.pixel_at(4, 4).0 < 1.);
+        assert!(blurred.pixel_at(3, 4).0 > 0.);
+        assert!(blurred.pixel_at(4, 5).0 > 0.);
+        assert!(blurred.pixel_at(3, 3).0 > 0.);
+
+        let total: f32 = blurred.into_iter().map(|c| c.0).sum();
+        assert_float_eq!(total, 1., abs <= 1e-4);
+    }
+
+    #[test]
+    fn bloom_adds_a_halo_around_bright_pixels() {
+        let dim = Color::new(0.2, 0.2, 0.2);
+        let mut canv = Canvas::new(21, 21, None);
+        canv.write_pixel(10, 10, &Color::new(4., 4., 4.));
+        canv.write_pixel(0, 0, &dim);
+
+        let bloomed = canv.bloom(1., 1.);
+
+        assert!(bloomed.pixel_at(11, 10).0 > 0.);
+        assert!(bloomed.pixel_at(10, 12).0 > 0.);
+        assert!(bloomed.pixel_at(10, 10).0 > 4.);
+        assert!(*bloomed.pixel_at(0, 0) == dim);
+        assert!(*bloomed.pixel_at(20, 20) == Color::new(0., 0., 0.));
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn writes_frames_to_an_animated_gif() {
+        let frames = [
+            Canvas::new(4, 3, Some(Color::new(1., 0., 0.))),
+            Canvas::new(4, 3, Some(Color::new(0., 0., 1.))),
+        ];
+        let path = std::env::temp_dir().join("ray_tracer_challenge_frames.gif");
+        let path = path.to_str().unwrap();
+
+        super::write_gif(path, &frames, 100).unwrap();
+
+        let bytes = std::fs::read(path).unwrap();
+        assert!(bytes.starts_with(b"GIF89a"));
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn rejects_frames_that_cannot_make_a_gif() {
+        let path = std::env::temp_dir().join("ray_tracer_challenge_rejected.gif");
+        let path = path.to_str().unwrap();
+
+        let frames = [Canvas::new(4, 3, None), Canvas::new(3, 4, None)];
+        assert!(matches!(
+            super::write_gif(path, &frames, 100),
+            Err(super::GifError::SizeMismatch(SizeMismatch {
+                expected: (4, 3),
+                found: (3, 4)
+            }))
+        ));
+
+        assert!(matches!(
+            super::write_gif(path, &[], 100),
+            Err(super::GifError::NoFrames)
+        ));
+
+        let frames = [Canvas::new(65536, 1, None)];
+        assert!(matches!(
+            super::write_gif(path, &frames, 100),
+            Err(super::GifError::TooLarge((65536, 1)))
+        ));
+    }
+
+    #[test]
+    fn identical_canvases_have_no_difference() {
+        let canv1 = Canvas::new(4, 4, Some(Color::new(0.2, 0.4, 0.6)));
+        let canv2 = canv1.clone();
+
+        assert_eq!(canv1.diff(&canv2), Ok(0.));
+    }
+
+    #[test]
+    fn single_pixel_change_yields_small_difference() {
+        let canv1 = Canvas::new(4, 4, None);
+        let mut canv2 = canv1.clone();
+        canv2.write_pixel(1, 2, &Color::new(0.3, 0., 0.));
+
+        let diff = canv1.diff(&canv2).unwrap();
+
+        assert!(diff > 0.);
+        assert_float_eq!(diff, 0.3 / 48., abs <= 1e-7);
+    }
+
+    #[test]
+    fn diff_of_differently_sized_canvases_fails() {
+        let canv1 = Canvas::new(4, 4, None);
+        let canv2 = Canvas::new(4, 3, None);
+
+        assert_eq!(
+            canv1.diff(&canv2),
+            Err(SizeMismatch {
+                expected: (4, 4),
+                found: (4, 3)
+            })
+        );
+    }
+
+    #[test]
+    fn diff_mask_highlights_differing_pixels() {
+        let canv1 = Canvas::new(3, 3, Some(Color::new(0.5, 0.5, 0.5)));
+        let mut canv2 = canv1.clone();
+        canv2.write_pixel(1, 1, &Color::new(0.5, 0.9, 0.5));
+        canv2.write_pixel(2, 0, &Color::new(0.5, 0.51, 0.5));
+
+        let mask = canv1.diff_mask(&canv2, 0.1).unwrap();
+
+        for y in 0..3 {
+            for x in 0..3 {
+                let expected = if (x, y) == (1, 1) {
+                    Color::new(1., 0., 0.)
+                } else {
+                    Color::new(0., 0., 0.)
+                };
+                assert!(*mask.pixel_at(x, y) == expected);
+            }
+        }
+    }
+
+    #[test]
+    fn downsample_averages_blocks_of_pixels() {
+        let mut canv = Canvas::new(4, 4, None);
+        canv.write_pixel(0, 0, &Color::new(1., 0., 0.));
+        canv.write_pixel(1, 1, &Color::new(1., 0., 0.));
+        canv.write_pixel(3, 0, &Color::new(0., 0.4, 0.));
+        canv.write_pixels(&[
+            (0, 2, Color::new(1., 1., 1.)),
+            (1, 2, Color::new(1., 1., 1.)),
+            (0, 3, Color::new(1., 1., 1.)),
+            (1, 3, Color::new(1., 1., 1.)),
+        ]);
+
+        let small = canv.downsample(2).unwrap();
+
+        assert_eq!(small.dimensions(), (2, 2));
+        assert!(*small.pixel_at(0, 0) == Color::new(0.5, 0., 0.));
+        assert!(*small.pixel_at(1, 0) == Color::new(0., 0.1, 0.));
+        assert!(*small.pixel_at(0, 1) == Color::new(1., 1., 1.));
+        assert!(*small.pixel_at(1, 1) == Color::new(0., 0., 0.));
+    }
+
+    #[test]
+    fn downsample_requires_divisible_dimensions() {
+        let canv = Canvas::new(4, 3, None);
+
+        assert_eq!(
+            canv.downsample(2).err(),
+            Some(DivisibilityError {
+                factor: 2,
+                dimensions: (4, 3)
+            })
+        );
+    }
 }
@@ -0,0 +1,5 @@
+pub mod canvas;
+pub mod math;
+pub mod ray;
+pub mod sim;
+pub mod uv;
@@ -1,13 +1,9 @@
-use crate::{
+use ray_tracer_challenge::{
     canvas::Canvas,
     math::{Point, Vector},
     sim::{Environment, Projectile, Simulator},
 };
 
-mod canvas;
-mod math;
-mod sim;
-
 fn main() {
     println!("Canon ball initialization...\n");
 
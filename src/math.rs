@@ -1,10 +1,41 @@
 use std::{
-    f32::EPSILON,
-    ops::{Add, AddAssign, Deref, DerefMut, Div, Mul, Neg, Sub},
+    error::Error,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Deref, DerefMut, Div, Mul, MulAssign, Neg, Sub},
 };
 
 use float_eq::float_eq;
 
+pub const EPSILON: f32 = 0.00001;
+
+pub trait Float:
+    Copy
+    + PartialOrd
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+{
+}
+
+impl Float for f32 {}
+
+impl Float for f64 {}
+
+fn xyz_to_le_bytes(x: f32, y: f32, z: f32) -> [u8; 12] {
+    let mut bytes = [0; 12];
+    bytes[0..4].copy_from_slice(&x.to_le_bytes());
+    bytes[4..8].copy_from_slice(&y.to_le_bytes());
+    bytes[8..12].copy_from_slice(&z.to_le_bytes());
+    bytes
+}
+
+fn xyz_from_le_bytes(bytes: [u8; 12]) -> (f32, f32, f32) {
+    let component =
+        |i: usize| f32::from_le_bytes([bytes[i], bytes[i + 1], bytes[i + 2], bytes[i + 3]]);
+    (component(0), component(4), component(8))
+}
+
 #[derive(Clone)]
 pub struct Point(pub f32, pub f32, pub f32);
 
@@ -12,6 +43,25 @@ impl Point {
     pub fn new(x: f32, y: f32, z: f32) -> Self {
         Self(x, y, z)
     }
+
+    pub fn rotated(&self, m: &Matrix4) -> Self {
+        m * self
+    }
+
+    pub fn reflect_across_plane(&self, plane_point: &Point, plane_normal: &Vector) -> Self {
+        let normal = plane_normal.normalize();
+        let distance = (self - plane_point).dot(&normal);
+        self - &(&normal * (2. * distance))
+    }
+
+    pub fn to_le_bytes(&self) -> [u8; 12] {
+        xyz_to_le_bytes(self.0, self.1, self.2)
+    }
+
+    pub fn from_le_bytes(bytes: [u8; 12]) -> Self {
+        let (x, y, z) = xyz_from_le_bytes(bytes);
+        Self(x, y, z)
+    }
 }
 
 impl PartialEq for Point {
@@ -22,6 +72,12 @@ impl PartialEq for Point {
     }
 }
 
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_tuple(f, self.0, self.1, self.2)
+    }
+}
+
 impl Add<&Vector> for &Point {
     type Output = Point;
 
@@ -66,6 +122,45 @@ impl Sub<&Vector> for &Point {
     }
 }
 
+fn fmt_tuple(f: &mut fmt::Formatter<'_>, x: f32, y: f32, z: f32) -> fmt::Result {
+    match f.precision() {
+        Some(p) => write!(f, "({:.*}, {:.*}, {:.*})", p, x, p, y, p, z),
+        None => write!(f, "({}, {}, {})", x, y, z),
+    }
+}
+
+pub fn centroid(points: &[Point]) -> Option<Point> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let mut sum = Point(0., 0., 0.);
+    for p in points {
+        sum.0 += p.0;
+        sum.1 += p.1;
+        sum.2 += p.2;
+    }
+
+    let n = points.len() as f32;
+    Some(Point(sum.0 / n, sum.1 / n, sum.2 / n))
+}
+
+pub fn bounds(points: &[Point]) -> Option<(Point, Point)> {
+    if points.is_empty() {
+        return None;
+    }
+
+    let mut min = Point(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+    let mut max = Point(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+    for p in points {
+        min = Point(min.0.min(p.0), min.1.min(p.1), min.2.min(p.2));
+        max = Point(max.0.max(p.0), max.1.max(p.1), max.2.max(p.2));
+    }
+
+    Some((min, max))
+}
+
 #[derive(Clone)]
 pub struct Vector(pub f32, pub f32, pub f32);
 
@@ -74,6 +169,15 @@ impl Vector {
         Self(x, y, z)
     }
 
+    pub fn to_le_bytes(&self) -> [u8; 12] {
+        xyz_to_le_bytes(self.0, self.1, self.2)
+    }
+
+    pub fn from_le_bytes(bytes: [u8; 12]) -> Self {
+        let (x, y, z) = xyz_from_le_bytes(bytes);
+        Self(x, y, z)
+    }
+
     pub fn magnitude(&self) -> f32 {
         (self.0.powi(2) + self.1.powi(2) + self.2.powi(2)).sqrt()
     }
@@ -83,10 +187,46 @@ impl Vector {
         Self(self.0 / mag, self.1 / mag, self.2 / mag)
     }
 
+    pub fn normalize_or_zero(&self) -> Self {
+        let mag = self.magnitude();
+        if mag < EPSILON {
+            return Self(0., 0., 0.);
+        }
+        Self(self.0 / mag, self.1 / mag, self.2 / mag)
+    }
+
+    pub fn is_unit(&self, eps: f32) -> bool {
+        (self.magnitude() - 1.).abs() <= eps
+    }
+
     pub fn dot(&self, vec: &Vector) -> f32 {
         self.0 * vec.0 + self.1 * vec.1 + self.2 * vec.2
     }
 
+    pub fn min(&self, other: &Vector) -> Self {
+        Self(
+            self.0.min(other.0),
+            self.1.min(other.1),
+            self.2.min(other.2),
+        )
+    }
+
+    pub fn max(&self, other: &Vector) -> Self {
+        Self(
+            self.0.max(other.0),
+            self.1.max(other.1),
+            self.2.max(other.2),
+        )
+    }
+
+    pub fn abs(&self) -> Self {
+        Self(self.0.abs(), self.1.abs(), self.2.abs())
+    }
+
+    pub fn transformed(&self, m: &Matrix4) -> Self {
+        m * self
+    }
+
     pub fn cross(&self, vec: &Vector) -> Self {
         let x = self.1 * vec.2 - self.2 * vec.1;
         let y = self.2 * vec.0 - self.0 * vec.2;
@@ -103,6 +243,12 @@ impl PartialEq for Vector {
     }
 }
 
+impl Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_tuple(f, self.0, self.1, self.2)
+    }
+}
+
 impl Add<&Vector> for &Vector {
     type Output = Vector;
 
@@ -181,6 +327,26 @@ impl Div<f32> for &Vector {
 
 pub struct Matrix<const N: usize>([[f32; N]; N]);
 
+pub type Matrix4 = Matrix<4>;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct LenError {
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Display for LenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected {} elements, found {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for LenError {}
+
 pub trait Matrixable<const N: usize> {
     fn new(arr: [[f32; N]; N]) -> Self;
 }
@@ -245,10 +411,16 @@ impl<const N: usize> Mul for &Matrix<N> {
     }
 }
 
-impl<const N: usize> PartialEq for Matrix<N> {
-    fn eq(&self, other: &Self) -> bool {
+impl<const N: usize> MulAssign<&Matrix<N>> for Matrix<N> {
+    fn mul_assign(&mut self, rhs: &Matrix<N>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<const N: usize> Matrix<N> {
+    pub fn approx_eq(&self, other: &Self, eps: f32) -> bool {
         for (a, b) in self.iter().flatten().zip(other.iter().flatten()) {
-            if !float_eq!(a, b, abs <= EPSILON) {
+            if !float_eq!(a, b, abs <= eps) {
                 return false;
             }
         }
@@ -256,22 +428,195 @@ impl<const N: usize> PartialEq for Matrix<N> {
     }
 }
 
+impl<const N: usize> PartialEq for Matrix<N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.approx_eq(other, EPSILON)
+    }
+}
+
+impl Matrix4 {
+    pub fn identity() -> Self {
+        Matrix::new([
+            [1., 0., 0., 0.],
+            [0., 1., 0., 0.],
+            [0., 0., 1., 0.],
+            [0., 0., 0., 1.],
+        ])
+    }
+
+    pub fn translation_part(&self) -> Vector {
+        Vector(self[0][3], self[1][3], self[2][3])
+    }
+
+    pub fn from_rows(rows: &[[f32; 4]; 4]) -> Self {
+        Self(*rows)
+    }
+
+    pub fn from_slice(values: &[f32]) -> Result<Self, LenError> {
+        if values.len() != 16 {
+            return Err(LenError {
+                expected: 16,
+                found: values.len(),
+            });
+        }
+
+        let mut res = Matrix::default();
+        for (i, value) in values.iter().enumerate() {
+            res[i / 4][i % 4] = *value;
+        }
+        Ok(res)
+    }
+}
+
+impl Mul<&Point> for &Matrix4 {
+    type Output = Point;
+
+    fn mul(self, rhs: &Point) -> Self::Output {
+        let x = self[0][0] * rhs.0 + self[0][1] * rhs.1 + self[0][2] * rhs.2 + self[0][3];
+        let y = self[1][0] * rhs.0 + self[1][1] * rhs.1 + self[1][2] * rhs.2 + self[1][3];
+        let z = self[2][0] * rhs.0 + self[2][1] * rhs.1 + self[2][2] * rhs.2 + self[2][3];
+
+        Point(x, y, z)
+    }
+}
+
+impl Mul<&Vector> for &Matrix4 {
+    type Output = Vector;
+
+    fn mul(self, rhs: &Vector) -> Self::Output {
+        let x = self[0][0] * rhs.0 + self[0][1] * rhs.1 + self[0][2] * rhs.2;
+        let y = self[1][0] * rhs.0 + self[1][1] * rhs.1 + self[1][2] * rhs.2;
+        let z = self[2][0] * rhs.0 + self[2][1] * rhs.1 + self[2][2] * rhs.2;
+
+        Vector(x, y, z)
+    }
+}
+
+pub fn translation(x: f32, y: f32, z: f32) -> Matrix4 {
+    Matrix::new([
+        [1., 0., 0., x],
+        [0., 1., 0., y],
+        [0., 0., 1., z],
+        [0., 0., 0., 1.],
+    ])
+}
+
+pub fn scaling(x: f32, y: f32, z: f32) -> Matrix4 {
+    Matrix::new([
+        [x, 0., 0., 0.],
+        [0., y, 0., 0.],
+        [0., 0., z, 0.],
+        [0., 0., 0., 1.],
+    ])
+}
+
+pub fn scaling_uniform(s: f32) -> Matrix4 {
+    scaling(s, s, s)
+}
+
+pub fn rotation_x(angle: f32) -> Matrix4 {
+    let (sin, cos) = angle.sin_cos();
+
+    Matrix::new([
+        [1., 0., 0., 0.],
+        [0., cos, -sin, 0.],
+        [0., sin, cos, 0.],
+        [0., 0., 0., 1.],
+    ])
+}
+
+pub fn rotation_y(angle: f32) -> Matrix4 {
+    let (sin, cos) = angle.sin_cos();
+
+    Matrix::new([
+        [cos, 0., sin, 0.],
+        [0., 1., 0., 0.],
+        [-sin, 0., cos, 0.],
+        [0., 0., 0., 1.],
+    ])
+}
+
+pub fn rotation_z(angle: f32) -> Matrix4 {
+    let (sin, cos) = angle.sin_cos();
+
+    Matrix::new([
+        [cos, -sin, 0., 0.],
+        [sin, cos, 0., 0.],
+        [0., 0., 1., 0.],
+        [0., 0., 0., 1.],
+    ])
+}
+
+// Rodrigues' rotation formula, the axis doesn't need to be normalized
+pub fn rotation_axis(axis: Vector, angle: f32) -> Matrix4 {
+    let Vector(x, y, z) = axis.normalize();
+    let (sin, cos) = angle.sin_cos();
+    let t = 1. - cos;
+
+    Matrix::new([
+        [
+            cos + x * x * t,
+            x * y * t - z * sin,
+            x * z * t + y * sin,
+            0.,
+        ],
+        [
+            y * x * t + z * sin,
+            cos + y * y * t,
+            y * z * t - x * sin,
+            0.,
+        ],
+        [
+            z * x * t - y * sin,
+            z * y * t + x * sin,
+            cos + z * z * t,
+            0.,
+        ],
+        [0., 0., 0., 1.],
+    ])
+}
+
 #[cfg(test)]
 mod test {
-    use std::f32::EPSILON;
+    use std::f32::consts::PI;
 
     use float_eq::assert_float_eq;
 
-    use crate::math::{Matrix, Matrixable, Point, Vector};
+    use crate::math::{
+        bounds, centroid, rotation_axis, rotation_x, rotation_z, scaling, scaling_uniform,
+        translation, LenError, Matrix, Matrix4, Matrixable, Point, Vector, EPSILON,
+    };
 
     #[test]
     fn create_a_point() {
         let ref_point = Point(1., 2., 3.);
         let my_point = Point::new(1., 2., 3.);
 
-        assert_float_eq!(my_point.0, ref_point.0, abs <= EPSILON);
-        assert_float_eq!(my_point.1, ref_point.1, abs <= EPSILON);
-        assert_float_eq!(my_point.2, ref_point.2, abs <= EPSILON);
+        assert_float_eq!(my_point.0, ref_point.0, abs <= f32::EPSILON);
+        assert_float_eq!(my_point.1, ref_point.1, abs <= f32::EPSILON);
+        assert_float_eq!(my_point.2, ref_point.2, abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn computes_centroid_and_bounds_of_points() {
+        let points = [
+            Point::new(1., 2., 3.),
+            Point::new(-1., 0., 5.),
+            Point::new(3., -2., 1.),
+            Point::new(1., 4., -1.),
+        ];
+
+        let (min, max) = bounds(&points).unwrap();
+
+        assert!(centroid(&points).unwrap() == Point::new(1., 1., 2.));
+        assert!(min == Point::new(-1., -2., -1.));
+        assert!(max == Point::new(3., 4., 5.));
+    }
+
+    #[test]
+    fn no_centroid_or_bounds_without_points() {
+        assert!(centroid(&[]).is_none());
+        assert!(bounds(&[]).is_none());
     }
 
     #[test]
@@ -279,9 +624,18 @@ mod test {
         let ref_vec = Vector(1., 2., 3.);
         let my_vec = Vector::new(1., 2., 3.);
 
-        assert_float_eq!(my_vec.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(my_vec.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(my_vec.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(my_vec.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(my_vec.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(my_vec.2, ref_vec.2, abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn displays_point_and_vector() {
+        assert_eq!(format!("{}", Point::new(1., -2.5, 3.)), "(1, -2.5, 3)");
+        assert_eq!(
+            format!("{:.2}", Vector::new(1., 2., 3.)),
+            "(1.00, 2.00, 3.00)"
+        );
     }
 
     #[test]
@@ -292,9 +646,9 @@ mod test {
 
         let ref_dest = Point::new(1., 1., 6.);
 
-        assert_float_eq!(dest.0, ref_dest.0, abs <= EPSILON);
-        assert_float_eq!(dest.1, ref_dest.1, abs <= EPSILON);
-        assert_float_eq!(dest.2, ref_dest.2, abs <= EPSILON);
+        assert_float_eq!(dest.0, ref_dest.0, abs <= f32::EPSILON);
+        assert_float_eq!(dest.1, ref_dest.1, abs <= f32::EPSILON);
+        assert_float_eq!(dest.2, ref_dest.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -305,9 +659,9 @@ mod test {
 
         let ref_vec = Vector::new(-2., -4., -6.);
 
-        assert_float_eq!(my_vec.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(my_vec.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(my_vec.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(my_vec.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(my_vec.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(my_vec.2, ref_vec.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -318,9 +672,9 @@ mod test {
 
         let ref_dest = Point::new(-2., -4., -6.);
 
-        assert_float_eq!(dest.0, ref_dest.0, abs <= EPSILON);
-        assert_float_eq!(dest.1, ref_dest.1, abs <= EPSILON);
-        assert_float_eq!(dest.2, ref_dest.2, abs <= EPSILON);
+        assert_float_eq!(dest.0, ref_dest.0, abs <= f32::EPSILON);
+        assert_float_eq!(dest.1, ref_dest.1, abs <= f32::EPSILON);
+        assert_float_eq!(dest.2, ref_dest.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -331,9 +685,9 @@ mod test {
 
         let ref_point = Point::new(-2., -4., -6.);
 
-        assert_float_eq!(point.0, ref_point.0, abs <= EPSILON);
-        assert_float_eq!(point.1, ref_point.1, abs <= EPSILON);
-        assert_float_eq!(point.2, ref_point.2, abs <= EPSILON);
+        assert_float_eq!(point.0, ref_point.0, abs <= f32::EPSILON);
+        assert_float_eq!(point.1, ref_point.1, abs <= f32::EPSILON);
+        assert_float_eq!(point.2, ref_point.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -342,9 +696,9 @@ mod test {
 
         let ref_vec = Vector::new(-5., -6., -7.);
 
-        assert_float_eq!(v.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(v.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(v.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(v.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(v.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(v.2, ref_vec.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -355,9 +709,9 @@ mod test {
 
         let ref_vec = Vector::new(2., -4., 6.);
 
-        assert_float_eq!(v2.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(v2.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(v2.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(v2.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(v2.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(v2.2, ref_vec.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -368,51 +722,51 @@ mod test {
 
         let ref_vec = Vector::new(0.5, -1., 1.5);
 
-        assert_float_eq!(v2.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(v2.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(v2.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(v2.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(v2.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(v2.2, ref_vec.2, abs <= f32::EPSILON);
     }
 
     #[test]
     fn computes_magnitude_of_vector_v1() {
         let v = Vector::new(0., 1., 0.);
-        assert_float_eq!(v.magnitude(), 1., abs <= EPSILON);
+        assert_float_eq!(v.magnitude(), 1., abs <= f32::EPSILON);
     }
 
     #[test]
     fn computes_magnitude_of_vector_v2() {
         let v = Vector::new(0., 1., 0.);
-        assert_float_eq!(v.magnitude(), 1., abs <= EPSILON);
+        assert_float_eq!(v.magnitude(), 1., abs <= f32::EPSILON);
     }
 
     #[test]
     fn computes_magnitude_of_vector_v3() {
         let v = Vector::new(1., 2., 3.);
-        assert_float_eq!(v.magnitude(), 14_f32.sqrt(), abs <= EPSILON);
+        assert_float_eq!(v.magnitude(), 14_f32.sqrt(), abs <= f32::EPSILON);
     }
 
     #[test]
     fn computes_magnitude_of_vector_v4() {
         let v = Vector::new(-1., -2., 3.);
-        assert_float_eq!(v.magnitude(), 14_f32.sqrt(), abs <= EPSILON);
+        assert_float_eq!(v.magnitude(), 14_f32.sqrt(), abs <= f32::EPSILON);
     }
 
     #[test]
     fn normalize_vector_v1() {
         let v = Vector::new(4., 0., 0.);
         let normalized_v = v.normalize();
-        assert_float_eq!(normalized_v.0, 1., abs <= EPSILON);
-        assert_float_eq!(normalized_v.1, 0., abs <= EPSILON);
-        assert_float_eq!(normalized_v.2, 0., abs <= EPSILON);
+        assert_float_eq!(normalized_v.0, 1., abs <= f32::EPSILON);
+        assert_float_eq!(normalized_v.1, 0., abs <= f32::EPSILON);
+        assert_float_eq!(normalized_v.2, 0., abs <= f32::EPSILON);
     }
 
     #[test]
     fn normalize_vector_v2() {
         let v = Vector::new(1., 2., 3.);
         let normalized_v = v.normalize();
-        assert_float_eq!(normalized_v.0, 1. / 14_f32.sqrt(), abs <= EPSILON);
-        assert_float_eq!(normalized_v.1, 2. / 14_f32.sqrt(), abs <= EPSILON);
-        assert_float_eq!(normalized_v.2, 3. / 14_f32.sqrt(), abs <= EPSILON);
+        assert_float_eq!(normalized_v.0, 1. / 14_f32.sqrt(), abs <= f32::EPSILON);
+        assert_float_eq!(normalized_v.1, 2. / 14_f32.sqrt(), abs <= f32::EPSILON);
+        assert_float_eq!(normalized_v.2, 3. / 14_f32.sqrt(), abs <= f32::EPSILON);
     }
 
     #[test]
@@ -420,7 +774,22 @@ mod test {
         let v = Vector::new(1., 2., 3.);
         let normalized_v = v.normalize();
         let magnitude = normalized_v.magnitude();
-        assert_float_eq!(magnitude, 1., abs <= EPSILON);
+        assert_float_eq!(magnitude, 1., abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn normalize_zero_vector_or_zero() {
+        let v = Vector::new(0., 0., 0.);
+
+        assert!(v.normalize_or_zero() == Vector::new(0., 0., 0.));
+    }
+
+    #[test]
+    fn normalized_vector_is_unit() {
+        let v = Vector::new(1., 2., 3.);
+
+        assert!(v.normalize_or_zero().is_unit(1e-5));
+        assert!(!v.is_unit(1e-5));
     }
 
     #[test]
@@ -430,7 +799,23 @@ mod test {
 
         let dot_res = v1.dot(&v2);
 
-        assert_float_eq!(dot_res, 20., abs <= EPSILON);
+        assert_float_eq!(dot_res, 20., abs <= f32::EPSILON);
+    }
+
+    #[test]
+    fn component_wise_min_and_max_of_vectors() {
+        let v1 = Vector::new(1., 5., 3.);
+        let v2 = Vector::new(4., 2., 6.);
+
+        assert!(v1.min(&v2) == Vector::new(1., 2., 3.));
+        assert!(v1.max(&v2) == Vector::new(4., 5., 6.));
+    }
+
+    #[test]
+    fn absolute_value_of_vector() {
+        let v = Vector::new(-1., 2., -3.);
+
+        assert!(v.abs() == Vector::new(1., 2., 3.));
     }
 
     #[test]
@@ -442,9 +827,9 @@ mod test {
 
         let ref_vec = Vector::new(-1., 2., -1.);
 
-        assert_float_eq!(v3.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(v3.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(v3.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(v3.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(v3.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(v3.2, ref_vec.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -456,9 +841,9 @@ mod test {
 
         let ref_vec = Vector::new(1., -2., 1.);
 
-        assert_float_eq!(v3.0, ref_vec.0, abs <= EPSILON);
-        assert_float_eq!(v3.1, ref_vec.1, abs <= EPSILON);
-        assert_float_eq!(v3.2, ref_vec.2, abs <= EPSILON);
+        assert_float_eq!(v3.0, ref_vec.0, abs <= f32::EPSILON);
+        assert_float_eq!(v3.1, ref_vec.1, abs <= f32::EPSILON);
+        assert_float_eq!(v3.2, ref_vec.2, abs <= f32::EPSILON);
     }
 
     #[test]
@@ -470,34 +855,34 @@ mod test {
             [13.5, 14.5, 15.5, 16.5],
         ]);
 
-        assert_float_eq!(m[0][0], 1., abs <= EPSILON);
-        assert_float_eq!(m[0][3], 4., abs <= EPSILON);
-        assert_float_eq!(m[1][0], 5.5, abs <= EPSILON);
-        assert_float_eq!(m[1][2], 7.5, abs <= EPSILON);
-        assert_float_eq!(m[2][2], 11., abs <= EPSILON);
-        assert_float_eq!(m[3][0], 13.5, abs <= EPSILON);
-        assert_float_eq!(m[3][2], 15.5, abs <= EPSILON);
+        assert_float_eq!(m[0][0], 1., abs <= f32::EPSILON);
+        assert_float_eq!(m[0][3], 4., abs <= f32::EPSILON);
+        assert_float_eq!(m[1][0], 5.5, abs <= f32::EPSILON);
+        assert_float_eq!(m[1][2], 7.5, abs <= f32::EPSILON);
+        assert_float_eq!(m[2][2], 11., abs <= f32::EPSILON);
+        assert_float_eq!(m[3][0], 13.5, abs <= f32::EPSILON);
+        assert_float_eq!(m[3][2], 15.5, abs <= f32::EPSILON);
     }
 
     #[test]
     fn constructs_and_inspects_3x3_matrix() {
         let m = Matrix::new([[-3., 5., 0.], [1., -2., -7.], [0., 0., 1.]]);
 
-        assert_float_eq!(m[0][0], -3., abs <= EPSILON);
-        assert_float_eq!(m[0][1], 5., abs <= EPSILON);
-        assert_float_eq!(m[1][0], 1., abs <= EPSILON);
-        assert_float_eq!(m[1][1], -2., abs <= EPSILON);
-        assert_float_eq!(m[2][2], 1., abs <= EPSILON);
+        assert_float_eq!(m[0][0], -3., abs <= f32::EPSILON);
+        assert_float_eq!(m[0][1], 5., abs <= f32::EPSILON);
+        assert_float_eq!(m[1][0], 1., abs <= f32::EPSILON);
+        assert_float_eq!(m[1][1], -2., abs <= f32::EPSILON);
+        assert_float_eq!(m[2][2], 1., abs <= f32::EPSILON);
     }
 
     #[test]
     fn constructs_and_inspects_2x2_matrix() {
         let m = Matrix::new([[-3., 5.], [1., -2.]]);
 
-        assert_float_eq!(m[0][0], -3., abs <= EPSILON);
-        assert_float_eq!(m[0][1], 5., abs <= EPSILON);
-        assert_float_eq!(m[1][0], 1., abs <= EPSILON);
-        assert_float_eq!(m[1][1], -2., abs <= EPSILON);
+        assert_float_eq!(m[0][0], -3., abs <= f32::EPSILON);
+        assert_float_eq!(m[0][1], 5., abs <= f32::EPSILON);
+        assert_float_eq!(m[1][0], 1., abs <= f32::EPSILON);
+        assert_float_eq!(m[1][1], -2., abs <= f32::EPSILON);
     }
 
     #[test]
@@ -564,4 +949,214 @@ mod test {
         let m4 = m1 * m2;
         assert!(m4 == m3);
     }
+
+    #[test]
+    fn constructs_4x4_matrix_from_rows() {
+        let rows = [
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [9., 8., 7., 6.],
+            [5., 4., 3., 2.],
+        ];
+
+        let m = Matrix4::from_rows(&rows);
+
+        assert!(m == Matrix::new(rows));
+    }
+
+    #[test]
+    fn constructs_4x4_matrix_from_row_major_slice() {
+        let values = [
+            1., 2., 3., 4., 5.5, 6.5, 7.5, 8.5, 9., 10., 11., 12., 13.5, 14.5, 15.5, 16.5,
+        ];
+
+        let m = Matrix4::from_slice(&values).unwrap();
+
+        let ref_m = Matrix4::from_rows(&[
+            [1., 2., 3., 4.],
+            [5.5, 6.5, 7.5, 8.5],
+            [9., 10., 11., 12.],
+            [13.5, 14.5, 15.5, 16.5],
+        ]);
+
+        assert!(m == ref_m);
+    }
+
+    #[test]
+    fn fails_to_construct_4x4_matrix_from_short_slice() {
+        let values = [0.; 15];
+
+        let res = Matrix4::from_slice(&values);
+
+        assert_eq!(
+            res.err(),
+            Some(LenError {
+                expected: 16,
+                found: 15
+            })
+        );
+    }
+
+    #[test]
+    fn rotation_around_z_axis_matches_rotation_z() {
+        let angle = PI / 4.;
+
+        let m = rotation_axis(Vector::new(0., 0., 1.), angle);
+
+        assert!(m == rotation_z(angle));
+    }
+
+    #[test]
+    fn rotation_around_unnormalized_axis_is_normalized() {
+        let angle = PI / 3.;
+
+        let m = rotation_axis(Vector::new(5., 0., 0.), angle);
+
+        assert!(m == rotation_x(angle));
+    }
+
+    #[test]
+    fn uniform_scaling_applied_to_point() {
+        let m = scaling_uniform(3.);
+        let p = Point::new(1., 1., 1.);
+
+        assert!(&m * &p == Point::new(3., 3., 3.));
+        assert!(m == scaling(3., 3., 3.));
+    }
+
+    #[test]
+    fn matrices_within_tolerance_compare_equal() {
+        let m1 = Matrix4::identity();
+        let mut m2 = Matrix4::identity();
+        m2[1][2] = 0.000001;
+
+        assert!(m1 == m2);
+        assert!(m1.approx_eq(&m2, 0.000001));
+        assert!(!m1.approx_eq(&m2, 0.0000001));
+    }
+
+    #[test]
+    fn inverse_multiplied_by_original_is_approximately_identity() {
+        let m = Matrix4::from_rows(&[
+            [-5., 2., 6., -8.],
+            [1., -5., 1., 8.],
+            [7., 7., -6., -7.],
+            [1., -3., 7., 4.],
+        ]);
+        // inverse of m rounded to five decimals
+        let inv = Matrix4::from_rows(&[
+            [0.21805, 0.45113, 0.24060, -0.04511],
+            [-0.80827, -1.45677, -0.44361, 0.52068],
+            [-0.07895, -0.22368, -0.05263, 0.19737],
+            [-0.52256, -0.81391, -0.30075, 0.30639],
+        ]);
+
+        let product = &m * &inv;
+
+        assert!(product.approx_eq(&Matrix4::identity(), 0.0001));
+    }
+
+    #[test]
+    fn rotates_point_around_x_axis() {
+        let p = Point::new(0., 1., 0.);
+
+        let rotated = p.rotated(&rotation_x(PI / 2.));
+
+        assert_float_eq!(rotated.0, 0., abs <= EPSILON);
+        assert_float_eq!(rotated.1, 0., abs <= EPSILON);
+        assert_float_eq!(rotated.2, 1., abs <= EPSILON);
+    }
+
+    #[test]
+    fn transforming_vector_ignores_translation() {
+        let m = &translation(5., 0., 0.) * &scaling(2., 3., 4.);
+
+        let v = Vector::new(1., 1., 1.).transformed(&m);
+
+        assert!(v == Vector::new(2., 3., 4.));
+    }
+
+    #[test]
+    fn translation_moves_points() {
+        let p = Point::new(-3., 4., 5.);
+
+        assert!(&translation(5., -3., 2.) * &p == Point::new(2., 1., 7.));
+    }
+
+    #[test]
+    fn extracts_translation_part() {
+        let m = translation(2., 3., 4.);
+
+        assert!(m.translation_part() == Vector::new(2., 3., 4.));
+    }
+
+    #[test]
+    fn chains_owned_transforms() {
+        let by_ref = &translation(10., 5., 7.) * &scaling(2., 2., 2.);
+
+        let owned = translation(10., 5., 7.) * scaling(2., 2., 2.) * Matrix4::identity();
+
+        assert!(owned == by_ref);
+    }
+
+    #[test]
+    fn multiply_assign_identity_keeps_matrix() {
+        let m = Matrix4::from_rows(&[
+            [1., 2., 3., 4.],
+            [5., 6., 7., 8.],
+            [9., 8., 7., 6.],
+            [5., 4., 3., 2.],
+        ]);
+        let mut acc = Matrix4::from_rows(&m);
+
+        acc *= &Matrix4::identity();
+
+        assert!(acc == m);
+    }
+
+    #[test]
+    fn multiply_assign_accumulates_translations() {
+        let mut acc = translation(1., 2., 3.);
+
+        acc *= &translation(4., -1., 0.5);
+
+        assert!(acc.translation_part() == Vector::new(5., 1., 3.5));
+    }
+
+    #[test]
+    fn point_bytes_round_trip() {
+        let p = Point::new(1.5, -2.25, 1e-3);
+        let bytes = p.to_le_bytes();
+
+        assert!(Point::from_le_bytes(bytes) == p);
+        assert!(Point::from_le_bytes(bytes).to_le_bytes() == bytes);
+        assert!(bytes[0..4] == 1.5_f32.to_le_bytes());
+    }
+
+    #[test]
+    fn vector_bytes_round_trip() {
+        let v = Vector::new(-0.5, 4., 1e6);
+        let bytes = v.to_le_bytes();
+
+        assert!(Vector::from_le_bytes(bytes) == v);
+        assert!(Vector::from_le_bytes(bytes).to_le_bytes() == bytes);
+    }
+
+    #[test]
+    fn reflects_point_across_xz_plane() {
+        let p = Point::new(0., 2., 0.);
+
+        let reflected = p.reflect_across_plane(&Point::new(0., 0., 0.), &Vector::new(0., 1., 0.));
+
+        assert!(reflected == Point::new(0., -2., 0.));
+    }
+
+    #[test]
+    fn reflects_point_across_offset_plane() {
+        let p = Point::new(3., 2., 5.);
+
+        let reflected = p.reflect_across_plane(&Point::new(7., 1., -4.), &Vector::new(0., 2., 0.));
+
+        assert!(reflected == Point::new(3., 0., 5.));
+    }
 }
@@ -0,0 +1,136 @@
+use crate::math::{Point, Vector, EPSILON};
+
+#[derive(Clone)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Vector,
+}
+
+impl Ray {
+    pub fn new(origin: Point, direction: Vector) -> Self {
+        Self { origin, direction }
+    }
+
+    pub fn position(&self, t: f32) -> Point {
+        &self.origin + &(t * &self.direction)
+    }
+}
+
+pub fn intersect_ray_plane(ray: &Ray, plane_point: &Point, plane_normal: &Vector) -> Option<f32> {
+    let denom = plane_normal.dot(&ray.direction);
+
+    // parallel or coplanar rays never cross the plane
+    if denom.abs() < EPSILON {
+        return None;
+    }
+
+    Some(plane_normal.dot(&(plane_point - &ray.origin)) / denom)
+}
+
+// Möller–Trumbore ray-triangle intersection
+pub fn intersect_ray_triangle(ray: &Ray, p1: &Point, p2: &Point, p3: &Point) -> Option<f32> {
+    let e1 = p2 - p1;
+    let e2 = p3 - p1;
+
+    let dir_cross_e2 = ray.direction.cross(&e2);
+    let det = e1.dot(&dir_cross_e2);
+    if det.abs() < EPSILON {
+        return None;
+    }
+
+    let f = 1. / det;
+    let p1_to_origin = &ray.origin - p1;
+    let u = f * p1_to_origin.dot(&dir_cross_e2);
+    if !(0. ..=1.).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin.cross(&e1);
+    let v = f * ray.direction.dot(&origin_cross_e1);
+    if v < 0. || u + v > 1. {
+        return None;
+    }
+
+    Some(f * e2.dot(&origin_cross_e1))
+}
+
+#[cfg(test)]
+mod test {
+    use float_eq::assert_float_eq;
+
+    use crate::math::{Point, Vector};
+
+    use super::{intersect_ray_plane, intersect_ray_triangle, Ray};
+
+    #[test]
+    fn computes_point_from_distance() {
+        let r = Ray::new(Point::new(2., 3., 4.), Vector::new(1., 0., 0.));
+
+        assert!(r.position(0.) == Point::new(2., 3., 4.));
+        assert!(r.position(1.) == Point::new(3., 3., 4.));
+        assert!(r.position(-1.) == Point::new(1., 3., 4.));
+        assert!(r.position(2.5) == Point::new(4.5, 3., 4.));
+    }
+
+    #[test]
+    fn ray_parallel_or_coplanar_to_plane_misses() {
+        let origin = Point::new(0., 0., 0.);
+        let normal = Vector::new(0., 1., 0.);
+
+        let parallel = Ray::new(Point::new(0., 10., 0.), Vector::new(0., 0., 1.));
+        let coplanar = Ray::new(Point::new(0., 0., 0.), Vector::new(0., 0., 1.));
+
+        assert_eq!(intersect_ray_plane(&parallel, &origin, &normal), None);
+        assert_eq!(intersect_ray_plane(&coplanar, &origin, &normal), None);
+    }
+
+    #[test]
+    fn ray_intersects_plane_from_above_and_below() {
+        let origin = Point::new(0., 0., 0.);
+        let normal = Vector::new(0., 1., 0.);
+
+        let above = Ray::new(Point::new(0., 1., 0.), Vector::new(0., -1., 0.));
+        let below = Ray::new(Point::new(0., -1., 0.), Vector::new(0., 1., 0.));
+
+        assert_float_eq!(
+            intersect_ray_plane(&above, &origin, &normal).unwrap(),
+            1.,
+            abs <= f32::EPSILON
+        );
+        assert_float_eq!(
+            intersect_ray_plane(&below, &origin, &normal).unwrap(),
+            1.,
+            abs <= f32::EPSILON
+        );
+    }
+
+    #[test]
+    fn ray_misses_triangle() {
+        let p1 = Point::new(0., 1., 0.);
+        let p2 = Point::new(-1., 0., 0.);
+        let p3 = Point::new(1., 0., 0.);
+
+        let rays = [
+            Ray::new(Point::new(0., -1., -2.), Vector::new(0., 1., 0.)),
+            Ray::new(Point::new(1., 1., -2.), Vector::new(0., 0., 1.)),
+            Ray::new(Point::new(-1., 1., -2.), Vector::new(0., 0., 1.)),
+            Ray::new(Point::new(0., -1., -2.), Vector::new(0., 0., 1.)),
+        ];
+
+        for r in rays {
+            assert_eq!(intersect_ray_triangle(&r, &p1, &p2, &p3), None);
+        }
+    }
+
+    #[test]
+    fn ray_strikes_triangle() {
+        let p1 = Point::new(0., 1., 0.);
+        let p2 = Point::new(-1., 0., 0.);
+        let p3 = Point::new(1., 0., 0.);
+        let r = Ray::new(Point::new(0., 0.5, -2.), Vector::new(0., 0., 1.));
+
+        let t = intersect_ray_triangle(&r, &p1, &p2, &p3).unwrap();
+
+        assert_float_eq!(t, 2., abs <= f32::EPSILON);
+    }
+}
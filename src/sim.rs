@@ -8,25 +8,126 @@ pub struct Environment {
     pub wind: Vector,
 }
 
+// the simulator has no time step, so gravity is applied per tick: earth uses the
+// demo's 0.1 and the others keep their real ratio to it
+const EARTH_GRAVITY: f32 = 0.1;
+
+impl Environment {
+    pub fn earth() -> Self {
+        Self::without_wind(EARTH_GRAVITY)
+    }
+
+    pub fn moon() -> Self {
+        Self::without_wind(EARTH_GRAVITY * 1.62 / 9.81)
+    }
+
+    pub fn mars() -> Self {
+        Self::without_wind(EARTH_GRAVITY * 3.71 / 9.81)
+    }
+
+    fn without_wind(gravity: f32) -> Self {
+        Self {
+            gravity: Vector::new(0., -gravity, 0.),
+            wind: Vector::new(0., 0., 0.),
+        }
+    }
+}
+
 pub struct Projectile {
     pub pos: Point,
     pub v: Vector,
 }
 
+pub struct Record {
+    pub pos: Point,
+    pub v: Vector,
+    pub kinetic_energy: f32,
+}
+
 pub struct Simulator {
     env: Environment,
     proj: Projectile,
+    restitution: f32,
+    bounces: usize,
+    log: Option<Vec<Record>>,
 }
 
 impl Simulator {
     pub fn new(env: Environment, proj: Projectile) -> Self {
-        Self { env, proj }
+        Self {
+            env,
+            proj,
+            restitution: 0.,
+            bounces: 0,
+            log: None,
+        }
+    }
+
+    pub fn enable_log(&mut self) {
+        self.log.get_or_insert_with(Vec::new);
+    }
+
+    pub fn log(&self) -> &[Record] {
+        self.log.as_deref().unwrap_or_default()
+    }
+
+    pub fn set_bounces(&mut self, restitution: f32, bounces: usize) {
+        self.restitution = restitution;
+        self.bounces = bounces;
     }
+
     pub fn tick(&mut self) -> &Projectile {
+        self.advance();
+        self.bounce();
+        // logged after the bounce so records match the trajectory
+        self.record();
+
+        &self.proj
+    }
+
+    fn advance(&mut self) {
         self.proj.pos += &self.proj.v;
         self.proj.v += &self.env.gravity + &self.env.wind;
+    }
 
-        &self.proj
+    fn bounce(&mut self) {
+        if self.proj.pos.1 > 0. || self.bounces == 0 {
+            return;
+        }
+
+        self.bounces -= 1;
+        self.proj.pos.1 = 0.;
+        self.proj.v.1 = -self.proj.v.1 * self.restitution;
+    }
+
+    fn record(&mut self) {
+        if let Some(log) = &mut self.log {
+            // unit mass
+            let kinetic_energy = 0.5 * self.proj.v.magnitude().powi(2);
+            log.push(Record {
+                pos: self.proj.pos.clone(),
+                v: self.proj.v.clone(),
+                kinetic_energy,
+            });
+        }
+    }
+
+    pub fn run_until(&mut self, pred: impl Fn(&Projectile) -> bool) -> Vec<Point> {
+        let mut points = Vec::new();
+
+        loop {
+            points.push(self.tick().pos.clone());
+
+            if pred(&self.proj) {
+                return points;
+            }
+        }
+    }
+
+    pub fn trajectory(&mut self) -> Vec<Point> {
+        // bounces put the projectile back on the floor, so it only ends up
+        // below it once they are used up
+        self.run_until(|p| p.pos.1 < 0.)
     }
 
     pub fn draw(&mut self, canvas: &mut Canvas) -> Ppm {
@@ -35,22 +136,151 @@ impl Simulator {
         let c = Color::new(1., 0., 0.);
         let height = canvas.get_height();
 
-        loop {
-            let new_proj = self.tick();
+        for pos in self.trajectory() {
             println!("Still flying...");
+            canvas.write_pixel(pos.0 as usize, height - pos.1 as usize, &c);
+        }
+        println!("Hit ground !!!");
+
+        canvas.to_ppm()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use float_eq::assert_float_eq;
+
+    use crate::math::{Point, Vector};
+
+    use super::{Environment, Projectile, Simulator};
+
+    fn launch(v: Vector) -> Simulator {
+        let proj = Projectile {
+            pos: Point::new(0., 1., 0.),
+            v,
+        };
+        Simulator::new(Environment::earth(), proj)
+    }
+
+    #[test]
+    fn moon_gravity_is_a_sixth_of_earth() {
+        let earth = Environment::earth();
+        let moon = Environment::moon();
+
+        let ratio = earth.gravity.magnitude() / moon.gravity.magnitude();
+
+        assert_float_eq!(ratio, 6., abs <= 0.1);
+        assert!(moon.wind == Vector::new(0., 0., 0.));
+        assert!(Environment::mars().gravity.1 < 0.);
+    }
+
+    #[test]
+    fn earth_matches_the_demo_gravity_per_tick() {
+        let proj = Projectile {
+            pos: Point::new(0., 1., 0.),
+            v: 11.25 * Vector::new(1., 1.8, 0.).normalize(),
+        };
+        let earth = Environment::earth();
+        assert!(earth.gravity == Vector::new(0., -0.1, 0.));
+
+        let mut sim = Simulator::new(earth, proj);
+
+        assert!(sim.trajectory().len() > 150);
+    }
+
+    #[test]
+    fn projectile_bounces_off_the_floor() {
+        let mut sim = launch(Vector::new(1., 1., 0.));
+        sim.set_bounces(0.5, 2);
+
+        let trajectory = sim.trajectory();
+
+        let minima: Vec<f32> = trajectory
+            .windows(3)
+            .filter(|w| w[1].1 < w[0].1 && w[1].1 <= w[2].1)
+            .map(|w| w[1].1)
+            .collect();
+
+        assert_eq!(minima.len(), 2);
+        for y in minima {
+            assert_float_eq!(y, 0., abs <= f32::EPSILON);
+        }
+        assert!(trajectory.last().unwrap().1 <= 0.);
+    }
+
+    #[test]
+    fn ticking_bounces_like_the_trajectory() {
+        let new_sim = || {
+            let mut sim = launch(Vector::new(1., 1., 0.));
+            sim.set_bounces(0.5, 5);
+            sim
+        };
+
+        let trajectory = new_sim().trajectory();
+        let mut sim = new_sim();
+
+        for pos in &trajectory {
+            assert!(sim.tick().pos == *pos);
+        }
+        assert!(trajectory.len() > 60);
+    }
+
+    #[test]
+    fn runs_until_predicate_is_met() {
+        let mut sim = launch(Vector::new(1., 2., 0.));
+
+        let points = sim.run_until(|p| p.pos.0 > 10.);
+
+        let (last, before) = points.split_last().unwrap();
+        assert!(last.0 > 10.);
+        assert!(before.iter().all(|p| p.0 <= 10.));
+    }
 
-            canvas.write_pixel(
-                new_proj.pos.0 as usize,
-                height - new_proj.pos.1 as usize,
-                &c,
-            );
+    #[test]
+    fn runs_past_the_floor_when_asked() {
+        let mut sim = launch(Vector::new(1., 2., 0.));
 
-            if new_proj.pos.1 <= 0. {
-                println!("Hit ground !!!");
-                break;
+        let points = sim.run_until(|p| p.pos.1 < -5.);
+
+        let (last, before) = points.split_last().unwrap();
+        assert!(last.1 < -5.);
+        assert!(before.iter().any(|p| p.1 < 0.));
+    }
+
+    #[test]
+    fn kinetic_energy_falls_while_climbing_and_rises_while_falling() {
+        let mut sim = launch(Vector::new(1., 2., 0.));
+        sim.enable_log();
+
+        let trajectory = sim.trajectory();
+        let log = sim.log();
+
+        assert_eq!(log.len(), trajectory.len());
+        for w in log.windows(2) {
+            if w[0].v.1 > 0. && w[1].v.1 > 0. {
+                assert!(w[1].kinetic_energy < w[0].kinetic_energy);
+            }
+            if w[0].v.1 < 0. && w[1].v.1 < 0. {
+                assert!(w[1].kinetic_energy > w[0].kinetic_energy);
             }
         }
+        assert!(log.iter().any(|r| r.v.1 > 0.) && log.iter().any(|r| r.v.1 < 0.));
+    }
 
-        canvas.to_ppm()
+    #[test]
+    fn log_matches_trajectory_across_bounces() {
+        let mut sim = launch(Vector::new(1., 1., 0.));
+        sim.set_bounces(0.5, 2);
+        sim.enable_log();
+
+        let trajectory = sim.trajectory();
+        let log = sim.log();
+
+        assert_eq!(log.len(), trajectory.len());
+        for (record, pos) in log.iter().zip(&trajectory) {
+            assert!(record.pos == *pos);
+        }
+        let (_, flying) = log.split_last().unwrap();
+        assert!(flying.iter().all(|r| r.pos.1 >= 0.));
     }
 }
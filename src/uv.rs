@@ -0,0 +1,503 @@
+use std::f32::consts::PI;
+
+use crate::{
+    canvas::{Canvas, Color},
+    math::Point,
+};
+
+pub enum UvPattern {
+    Checkers {
+        width: f32,
+        height: f32,
+        a: Color,
+        b: Color,
+    },
+    AntialiasedCheckers {
+        width: f32,
+        height: f32,
+        a: Color,
+        b: Color,
+        radius: f32,
+    },
+    Image(Canvas),
+    Solid(Color),
+    Blend(Box<UvPattern>, Box<UvPattern>),
+}
+
+impl UvPattern {
+    pub fn checkers(width: f32, height: f32, a: Color, b: Color) -> Self {
+        Self::Checkers {
+            width,
+            height,
+            a,
+            b,
+        }
+    }
+
+    pub fn antialiased_checkers(width: f32, height: f32, a: Color, b: Color, radius: f32) -> Self {
+        Self::AntialiasedCheckers {
+            width,
+            height,
+            a,
+            b,
+            radius,
+        }
+    }
+
+    pub fn image(canvas: Canvas) -> Self {
+        Self::Image(canvas)
+    }
+
+    pub fn solid(color: Color) -> Self {
+        Self::Solid(color)
+    }
+
+    pub fn blend(a: UvPattern, b: UvPattern) -> Self {
+        Self::Blend(Box::new(a), Box::new(b))
+    }
+
+    pub fn pattern_at(&self, u: f32, v: f32) -> Color {
+        match self {
+            Self::Checkers {
+                width,
+                height,
+                a,
+                b,
+            } => checker_at(*width, *height, a, b, u, v).clone(),
+            Self::AntialiasedCheckers {
+                width,
+                height,
+                a,
+                b,
+                radius,
+            } => {
+                // average four samples around (u, v) to soften the cell edges
+                let mut color = Color::new(0., 0., 0.);
+                for (du, dv) in [(-1., -1.), (1., -1.), (-1., 1.), (1., 1.)] {
+                    color += checker_at(*width, *height, a, b, u + du * radius, v + dv * radius);
+                }
+                &color * 0.25
+            }
+            Self::Image(canvas) => {
+                if canvas.pixel_count() == 0 {
+                    return Color::new(0., 0., 0.);
+                }
+
+                // canvas rows go top to bottom while v goes bottom to top
+                let u = u.clamp(0., 1.);
+                let v = 1. - v.clamp(0., 1.);
+                let x = u * (canvas.get_width() - 1) as f32;
+                let y = v * (canvas.get_height() - 1) as f32;
+
+                canvas
+                    .pixel_at(x.round() as usize, y.round() as usize)
+                    .clone()
+            }
+            Self::Solid(color) => color.clone(),
+            Self::Blend(a, b) => &(&a.pattern_at(u, v) + &b.pattern_at(u, v)) * 0.5,
+        }
+    }
+}
+
+fn checker_at<'a>(
+    width: f32,
+    height: f32,
+    a: &'a Color,
+    b: &'a Color,
+    u: f32,
+    v: f32,
+) -> &'a Color {
+    let u2 = (u * width).floor();
+    let v2 = (v * height).floor();
+
+    if (u2 + v2).rem_euclid(2.) == 0. {
+        a
+    } else {
+        b
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum CubeFace {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UvMapping {
+    Spherical,
+    Planar,
+    Cylindrical,
+}
+
+impl UvMapping {
+    pub fn map(&self, p: &Point) -> (f32, f32) {
+        match self {
+            Self::Spherical => spherical_map(p),
+            Self::Planar => planar_map(p),
+            Self::Cylindrical => cylindrical_map(p),
+        }
+    }
+}
+
+pub fn spherical_map(p: &Point) -> (f32, f32) {
+    let theta = p.0.atan2(p.2);
+    let radius = (p.0.powi(2) + p.1.powi(2) + p.2.powi(2)).sqrt();
+    let phi = (p.1 / radius).acos();
+
+    let raw_u = theta / (2. * PI);
+    let u = 1. - (raw_u + 0.5);
+    let v = 1. - phi / PI;
+
+    (u, v)
+}
+
+pub fn planar_map(p: &Point) -> (f32, f32) {
+    (p.0.rem_euclid(1.), p.2.rem_euclid(1.))
+}
+
+pub fn cylindrical_map(p: &Point) -> (f32, f32) {
+    let theta = p.0.atan2(p.2);
+    let raw_u = theta / (2. * PI);
+    let u = 1. - (raw_u + 0.5);
+
+    (u, p.1.rem_euclid(1.))
+}
+
+pub fn truncated_cylindrical_map(p: &Point, minimum: f32, maximum: f32) -> (f32, f32) {
+    let (u, _) = cylindrical_map(p);
+    let v = (p.1 - minimum) / (maximum - minimum);
+
+    (u, v)
+}
+
+// a cone's side unwraps like a cylinder's, theta only depends on the direction around y
+pub fn conical_map(p: &Point, minimum: f32, maximum: f32) -> (f32, f32) {
+    truncated_cylindrical_map(p, minimum, maximum)
+}
+
+pub fn face_from_point(p: &Point) -> CubeFace {
+    let coord = p.0.abs().max(p.1.abs()).max(p.2.abs());
+
+    match coord {
+        c if c == p.0 => CubeFace::Right,
+        c if c == -p.0 => CubeFace::Left,
+        c if c == p.1 => CubeFace::Up,
+        c if c == -p.1 => CubeFace::Down,
+        c if c == p.2 => CubeFace::Front,
+        _ => CubeFace::Back,
+    }
+}
+
+pub fn cube_uv_front(p: &Point) -> (f32, f32) {
+    let u = (p.0 + 1.).rem_euclid(2.) / 2.;
+    let v = (p.1 + 1.).rem_euclid(2.) / 2.;
+    (u, v)
+}
+
+pub fn cube_uv_back(p: &Point) -> (f32, f32) {
+    let u = (1. - p.0).rem_euclid(2.) / 2.;
+    let v = (p.1 + 1.).rem_euclid(2.) / 2.;
+    (u, v)
+}
+
+pub fn cube_uv_left(p: &Point) -> (f32, f32) {
+    let u = (p.2 + 1.).rem_euclid(2.) / 2.;
+    let v = (p.1 + 1.).rem_euclid(2.) / 2.;
+    (u, v)
+}
+
+pub fn cube_uv_right(p: &Point) -> (f32, f32) {
+    let u = (1. - p.2).rem_euclid(2.) / 2.;
+    let v = (p.1 + 1.).rem_euclid(2.) / 2.;
+    (u, v)
+}
+
+pub fn cube_uv_up(p: &Point) -> (f32, f32) {
+    let u = (p.0 + 1.).rem_euclid(2.) / 2.;
+    let v = (1. - p.2).rem_euclid(2.) / 2.;
+    (u, v)
+}
+
+pub fn cube_uv_down(p: &Point) -> (f32, f32) {
+    let u = (p.0 + 1.).rem_euclid(2.) / 2.;
+    let v = (p.2 + 1.).rem_euclid(2.) / 2.;
+    (u, v)
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::PI;
+
+    use float_eq::assert_float_eq;
+
+    use crate::{
+        canvas::{Canvas, Color},
+        math::Point,
+    };
+
+    use super::{
+        conical_map, cube_uv_back, cube_uv_down, cube_uv_front, cube_uv_left, cube_uv_right,
+        cube_uv_up, cylindrical_map, face_from_point, planar_map, spherical_map,
+        truncated_cylindrical_map, CubeFace, UvMapping, UvPattern,
+    };
+
+    #[test]
+    fn checker_pattern_in_2d() {
+        let black = Color::new(0., 0., 0.);
+        let white = Color::new(1., 1., 1.);
+        let checkers = UvPattern::checkers(2., 2., black.clone(), white.clone());
+
+        assert!(checkers.pattern_at(0., 0.) == black);
+        assert!(checkers.pattern_at(0.5, 0.) == white);
+        assert!(checkers.pattern_at(0., 0.5) == white);
+        assert!(checkers.pattern_at(0.5, 0.5) == black);
+        assert!(checkers.pattern_at(1., 1.) == black);
+    }
+
+    #[test]
+    fn antialiased_checkers_blend_on_boundaries() {
+        let black = Color::new(0., 0., 0.);
+        let white = Color::new(1., 1., 1.);
+        let checkers = UvPattern::antialiased_checkers(2., 2., black.clone(), white.clone(), 0.01);
+
+        assert!(checkers.pattern_at(0.5, 0.25) == Color::new(0.5, 0.5, 0.5));
+        assert!(checkers.pattern_at(0.25, 0.25) == black);
+        assert!(checkers.pattern_at(0.75, 0.25) == white);
+    }
+
+    #[test]
+    fn spherical_mapping_on_3d_point() {
+        let half = 2_f32.sqrt() / 2.;
+        let cases = [
+            (Point::new(0., 0., -1.), 0., 0.5),
+            (Point::new(1., 0., 0.), 0.25, 0.5),
+            (Point::new(0., 0., 1.), 0.5, 0.5),
+            (Point::new(-1., 0., 0.), 0.75, 0.5),
+            (Point::new(0., 1., 0.), 0.5, 1.),
+            (Point::new(0., -1., 0.), 0.5, 0.),
+            (Point::new(half, half, 0.), 0.25, 0.75),
+        ];
+
+        for (p, ref_u, ref_v) in cases {
+            let (u, v) = spherical_map(&p);
+
+            assert_float_eq!(u, ref_u, abs <= 1e-5);
+            assert_float_eq!(v, ref_v, abs <= 1e-5);
+        }
+    }
+
+    #[test]
+    fn image_pattern_samples_canvas() {
+        let red = Color::new(1., 0., 0.);
+        let mut canv = Canvas::new(2, 2, None);
+        canv.write_pixel(0, 1, &red);
+        let image = UvPattern::image(canv);
+
+        assert!(image.pattern_at(0., 0.) == red);
+        assert!(image.pattern_at(0., 1.) == Color::new(0., 0., 0.));
+    }
+
+    #[test]
+    fn image_pattern_clamps_out_of_range_coordinates() {
+        let red = Color::new(1., 0., 0.);
+        let blue = Color::new(0., 0., 1.);
+        let mut canv = Canvas::new(4, 4, None);
+        canv.write_pixel(0, 3, &red);
+        canv.write_pixel(3, 0, &blue);
+        let image = UvPattern::image(canv);
+
+        assert!(image.pattern_at(0., -0.5) == red);
+        assert!(image.pattern_at(-2., 0.) == red);
+        assert!(image.pattern_at(1.5, 1.) == blue);
+        assert!(image.pattern_at(1., 7.) == blue);
+    }
+
+    #[test]
+    fn solid_pattern_ignores_coordinates() {
+        let color = Color::new(0.2, 0.4, 0.6);
+        let solid = UvPattern::solid(color.clone());
+
+        assert!(solid.pattern_at(0., 0.) == color);
+        assert!(solid.pattern_at(0.5, 0.25) == color);
+        assert!(solid.pattern_at(-3., 12.) == color);
+    }
+
+    #[test]
+    fn blend_pattern_averages_sub_patterns() {
+        let red = UvPattern::solid(Color::new(1., 0., 0.));
+        let blue = UvPattern::solid(Color::new(0., 0., 1.));
+        let purple = UvPattern::blend(red, blue);
+
+        assert!(purple.pattern_at(0., 0.) == Color::new(0.5, 0., 0.5));
+        assert!(purple.pattern_at(0.75, 0.3) == Color::new(0.5, 0., 0.5));
+    }
+
+    #[test]
+    fn blend_patterns_nest() {
+        let black = Color::new(0., 0., 0.);
+        let white = Color::new(1., 1., 1.);
+        let checkers = UvPattern::checkers(2., 2., black, white.clone());
+        let inner = UvPattern::blend(checkers, UvPattern::solid(white.clone()));
+        let outer = UvPattern::blend(inner, UvPattern::solid(white));
+
+        assert!(outer.pattern_at(0.25, 0.25) == Color::new(0.75, 0.75, 0.75));
+        assert!(outer.pattern_at(0.75, 0.25) == Color::new(1., 1., 1.));
+    }
+
+    #[test]
+    fn empty_image_pattern_is_black() {
+        let image = UvPattern::image(Canvas::new(0, 0, None));
+
+        assert!(image.pattern_at(0.5, 0.5) == Color::new(0., 0., 0.));
+    }
+
+    #[test]
+    fn planar_mapping_on_3d_point() {
+        let cases = [
+            (Point::new(0.25, 0., 0.5), 0.25, 0.5),
+            (Point::new(0.25, 0., -0.25), 0.25, 0.75),
+            (Point::new(0.25, 0.5, -0.25), 0.25, 0.75),
+            (Point::new(1.25, 0., 0.5), 0.25, 0.5),
+            (Point::new(0.25, 0., -1.75), 0.25, 0.25),
+            (Point::new(1., 0., -1.), 0., 0.),
+            (Point::new(0., 0., 0.), 0., 0.),
+        ];
+
+        for (p, ref_u, ref_v) in cases {
+            let (u, v) = planar_map(&p);
+
+            assert_float_eq!(u, ref_u, abs <= 1e-5);
+            assert_float_eq!(v, ref_v, abs <= 1e-5);
+        }
+    }
+
+    #[test]
+    fn cylindrical_mapping_on_3d_point() {
+        let half = 2_f32.sqrt() / 2.;
+        let cases = [
+            (Point::new(0., 0., -1.), 0., 0.),
+            (Point::new(0., 0.5, -1.), 0., 0.5),
+            (Point::new(0., 1., -1.), 0., 0.),
+            (Point::new(half, 0.5, -half), 0.125, 0.5),
+            (Point::new(1., 0.5, 0.), 0.25, 0.5),
+            (Point::new(half, 0.5, half), 0.375, 0.5),
+            (Point::new(0., -0.25, 1.), 0.5, 0.75),
+            (Point::new(-half, 0.5, half), 0.625, 0.5),
+            (Point::new(-1., 1.25, 0.), 0.75, 0.25),
+            (Point::new(-half, 0.5, -half), 0.875, 0.5),
+        ];
+
+        for (p, ref_u, ref_v) in cases {
+            let (u, v) = cylindrical_map(&p);
+
+            assert_float_eq!(u, ref_u, abs <= 1e-5);
+            assert_float_eq!(v, ref_v, abs <= 1e-5);
+        }
+    }
+
+    #[test]
+    fn mapping_selects_the_projection() {
+        let p = Point::new(0.25, 0.5, -0.25);
+
+        assert_eq!(UvMapping::Planar.map(&p), planar_map(&p));
+        assert_eq!(UvMapping::Cylindrical.map(&p), cylindrical_map(&p));
+        assert_eq!(UvMapping::Spherical.map(&p), spherical_map(&p));
+    }
+
+    #[test]
+    fn identifies_the_face_of_a_cube_from_a_point() {
+        let cases = [
+            (Point::new(-1., 0.5, -0.25), CubeFace::Left),
+            (Point::new(1.1, -0.75, 0.8), CubeFace::Right),
+            (Point::new(0.1, 0.6, 0.9), CubeFace::Front),
+            (Point::new(-0.7, 0., -2.), CubeFace::Back),
+            (Point::new(0.5, 1., 0.9), CubeFace::Up),
+            (Point::new(-0.2, -1.3, 1.1), CubeFace::Down),
+        ];
+
+        for (p, face) in cases {
+            assert_eq!(face_from_point(&p), face);
+        }
+    }
+
+    #[test]
+    fn uv_mapping_of_each_cube_face() {
+        let cases = [
+            (
+                cube_uv_front(&Point::new(-0.5, 0.5, 1.)),
+                cube_uv_front(&Point::new(0.5, -0.5, 1.)),
+            ),
+            (
+                cube_uv_back(&Point::new(0.5, 0.5, -1.)),
+                cube_uv_back(&Point::new(-0.5, -0.5, -1.)),
+            ),
+            (
+                cube_uv_left(&Point::new(-1., 0.5, -0.5)),
+                cube_uv_left(&Point::new(-1., -0.5, 0.5)),
+            ),
+            (
+                cube_uv_right(&Point::new(1., 0.5, 0.5)),
+                cube_uv_right(&Point::new(1., -0.5, -0.5)),
+            ),
+            (
+                cube_uv_up(&Point::new(-0.5, 1., -0.5)),
+                cube_uv_up(&Point::new(0.5, 1., 0.5)),
+            ),
+            (
+                cube_uv_down(&Point::new(-0.5, -1., 0.5)),
+                cube_uv_down(&Point::new(0.5, -1., -0.5)),
+            ),
+        ];
+
+        for (upper_left, lower_right) in cases {
+            assert_eq!(upper_left, (0.25, 0.75));
+            assert_eq!(lower_right, (0.75, 0.25));
+        }
+    }
+
+    #[test]
+    fn truncated_cylinder_side_unwraps_around_theta() {
+        let (u, v) = truncated_cylindrical_map(&Point::new(0., 1., -1.), 1., 3.);
+        assert_float_eq!(u, 0., abs <= 1e-5);
+        assert_float_eq!(v, 0., abs <= 1e-5);
+
+        let (_, v) = truncated_cylindrical_map(&Point::new(0., 2.5, -1.), 1., 3.);
+        assert_float_eq!(v, 0.75, abs <= 1e-5);
+
+        let mut previous = -1.;
+        for step in 0..16 {
+            let theta = step as f32 * PI / 8.;
+            let p = Point::new(theta.sin(), 2., -theta.cos());
+
+            let (u, _) = truncated_cylindrical_map(&p, 1., 3.);
+
+            assert!(u > previous);
+            previous = u;
+        }
+    }
+
+    #[test]
+    fn cone_side_unwraps_around_theta() {
+        let (u, v) = conical_map(&Point::new(0., -1., -1.), -1., 0.);
+        assert_float_eq!(u, 0., abs <= 1e-5);
+        assert_float_eq!(v, 0., abs <= 1e-5);
+
+        let mut previous = -1.;
+        for step in 0..16 {
+            let theta = step as f32 * PI / 8.;
+            let radius = 0.5;
+            let p = Point::new(radius * theta.sin(), -0.5, -radius * theta.cos());
+
+            let (u, v) = conical_map(&p, -1., 0.);
+
+            assert!(u > previous);
+            assert_float_eq!(v, 0.5, abs <= 1e-5);
+            previous = u;
+        }
+    }
+}